@@ -38,6 +38,7 @@ fn binary_add(b: &mut Bencher) {
         alg_kind: Binary::ADD,
         src0_desc: src0_desc.clone_desc().unwrap(),
         src1_desc: src1_desc.clone_desc().unwrap(),
+        src2_desc: None,
         dst_desc: dst_desc.clone_desc().unwrap(),
         attr: PrimitiveAttributes::new().unwrap(),
     };
@@ -283,5 +283,26 @@ impl<T> Drop for Memory<T> {
     }
 }
 
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A [`Memory`] with its element type erased, so that memories of different data types
+/// can be handed to a primitive together.
+///
+/// The handle is passed straight to oneDNN, so the trait is sealed and only implemented
+/// by [`Memory`].
+pub trait AnyMemory: sealed::Sealed {
+    fn handle(&self) -> dnnl_memory_t;
+}
+
+impl<T> sealed::Sealed for Memory<T> {}
+
+impl<T> AnyMemory for Memory<T> {
+    fn handle(&self) -> dnnl_memory_t {
+        self.handle
+    }
+}
+
 unsafe impl<T> Sync for Memory<T> {}
 unsafe impl<T> Send for Memory<T> {}
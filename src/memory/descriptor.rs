@@ -314,6 +314,11 @@ pub struct DataType;
 impl DataType {
     pub const F32: dnnl_data_type_t::Type = dnnl_data_type_t::dnnl_f32;
     pub const F64: dnnl_data_type_t::Type = dnnl_data_type_t::dnnl_f64;
+    pub const F16: dnnl_data_type_t::Type = dnnl_data_type_t::dnnl_f16;
+    pub const BF16: dnnl_data_type_t::Type = dnnl_data_type_t::dnnl_bf16;
+    pub const S32: dnnl_data_type_t::Type = dnnl_data_type_t::dnnl_s32;
+    pub const S8: dnnl_data_type_t::Type = dnnl_data_type_t::dnnl_s8;
+    pub const U8: dnnl_data_type_t::Type = dnnl_data_type_t::dnnl_u8;
 }
 
 /// Trait representing a query to be performed
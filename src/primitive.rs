@@ -1,10 +1,22 @@
 use {
-    crate::{engine::Engine, error::DnnlError, memory::Memory, stream::Stream},
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::{
+            descriptor::{DataType, MemoryDescriptor},
+            format_tag::a,
+            AnyMemory,
+        },
+        stream::Stream,
+    },
     config::PrimitiveConfig,
-    descriptor::PrimitiveDescriptor,
+    descriptor::{query_md, PrimitiveDescriptor},
     onednnl_sys::{
-        dnnl_exec_arg_t, dnnl_primitive_create, dnnl_primitive_destroy, dnnl_primitive_execute,
-        dnnl_primitive_t, dnnl_prop_kind_t, dnnl_status_t,
+        dnnl_dim_t, dnnl_exec_arg_t, dnnl_primitive_create, dnnl_primitive_destroy,
+        dnnl_primitive_execute, dnnl_primitive_get_primitive_desc, dnnl_primitive_t,
+        dnnl_prop_kind_t, dnnl_query_t, dnnl_status_t, DNNL_ARG_ATTR_DROPOUT_MASK,
+        DNNL_ARG_ATTR_DROPOUT_PROBABILITY, DNNL_ARG_ATTR_DROPOUT_SEED, DNNL_ARG_ATTR_ROUNDING_SEED,
+        DNNL_ARG_ATTR_SCALES, DNNL_ARG_ATTR_ZERO_POINTS, DNNL_ARG_SCRATCHPAD,
     },
     std::sync::Arc,
 };
@@ -12,6 +24,7 @@ use {
 pub mod attributes;
 pub mod config;
 pub mod descriptor;
+pub mod post_ops;
 
 pub trait Direction {
     const KIND: DirectionT;
@@ -47,13 +60,18 @@ pub enum OperationType {
     InnerProduct,
     LayerNormalization,
     LbrAuGru,
+    LbrGru,
     Lrn,
     Lstm,
     MatMul,
+    Pooling,
     PRelu,
     Reduction,
+    Reorder,
+    Resampling,
     Shuffle,
     Softmax,
+    Sum,
     VanillaRnn,
 }
 
@@ -75,10 +93,34 @@ pub struct PropBackwardWeights;
 #[derive(Debug, Clone, Copy)]
 pub struct PropBackwardData;
 
+/// The propagation kind of primitives that do not take one, such as concat, sum and
+/// reorder. These only run forward.
+///
+/// `PropAny` is not a [`PropType`], so it can only be used with the primitives that
+/// implement their config for it, and not with those that pass a propagation kind to
+/// oneDNN.
+///
+/// ```compile_fail
+/// use {
+///     onednnl::{
+///         engine::Engine,
+///         primitive::{config::PrimitiveConfig, Forward, PropAny},
+///         primitives::softmax::ForwardSoftmaxConfig,
+///     },
+///     std::sync::Arc,
+/// };
+///
+/// // Softmax takes a propagation kind, so it cannot be created with `PropAny`.
+/// fn softmax_without_prop_kind(config: ForwardSoftmaxConfig, engine: Arc<Engine>) {
+///     let _ = <ForwardSoftmaxConfig as PrimitiveConfig<Forward, PropAny>>::create_primitive_desc(
+///         config, engine,
+///     );
+/// }
+/// ```
 #[derive(Debug, Clone, Copy)]
 pub struct PropAny;
 
-pub trait Operation<'a, D: Direction, P: PropType<D>> {
+pub trait Operation<'a, D: Direction, P: Propagation<D>> {
     const TYPE: OperationType;
 
     type OperationConfig: PrimitiveConfig<'a, D, P>;
@@ -88,6 +130,14 @@ pub trait PropType<D> {
     const KIND: dnnl_prop_kind_t::Type;
 }
 
+/// Any propagation a primitive can be created with: a [`PropType`], which is passed on
+/// to oneDNN, or [`PropAny`] for the primitives without a propagation kind.
+pub trait Propagation<D> {}
+
+impl<D, P: PropType<D>> Propagation<D> for P {}
+
+impl Propagation<Forward> for PropAny {}
+
 impl PropType<Forward> for PropForwardInference {
     const KIND: dnnl_prop_kind_t::Type = dnnl_prop_kind_t::dnnl_forward_inference;
 }
@@ -108,13 +158,41 @@ impl PropType<Backward> for PropBackwardData {
     const KIND: dnnl_prop_kind_t::Type = dnnl_prop_kind_t::dnnl_backward_data;
 }
 
-pub struct Primitive<'a, D: Direction, P: PropType<D>, C: PrimitiveConfig<'a, D, P>> {
+/// The backward propagation kinds of the primitives that compute either all of their
+/// gradients, with [`PropBackward`], or only the one of their source, with
+/// [`PropBackwardData`], such as the normalizations.
+///
+/// ```compile_fail
+/// use {
+///     onednnl::{
+///         engine::Engine,
+///         primitive::{config::PrimitiveConfig, Backward, PropBackwardWeights},
+///         primitives::layer_norm::BackwardLayerNormConfig,
+///     },
+///     std::sync::Arc,
+/// };
+///
+/// // Layer normalization has no backward pass for the weights alone.
+/// fn layer_norm_weights_only(config: BackwardLayerNormConfig<'_>, engine: Arc<Engine>) {
+///     let _ = <BackwardLayerNormConfig as PrimitiveConfig<
+///         Backward,
+///         PropBackwardWeights,
+///     >>::create_primitive_desc(config, engine);
+/// }
+/// ```
+pub trait PropBackwardOrData: PropType<Backward> {}
+
+impl PropBackwardOrData for PropBackward {}
+
+impl PropBackwardOrData for PropBackwardData {}
+
+pub struct Primitive<'a, D: Direction, P: Propagation<D>, C: PrimitiveConfig<'a, D, P>> {
     pub handle: dnnl_primitive_t,
     pub desc: Option<PrimitiveDescriptor<'a, D, P, C>>,
     pub engine: Arc<Engine>,
 }
 
-impl<'a, D: Direction, P: PropType<D>, C: PrimitiveConfig<'a, D, P>> Primitive<'a, D, P, C> {
+impl<'a, D: Direction, P: Propagation<D>, C: PrimitiveConfig<'a, D, P>> Primitive<'a, D, P, C> {
     /// Creates a new `Primitive`.
     ///
     /// # Example
@@ -143,6 +221,7 @@ impl<'a, D: Direction, P: PropType<D>, C: PrimitiveConfig<'a, D, P>> Primitive<'
     ///     alg_kind: dnnl_alg_kind_t::dnnl_binary_add, // Example: addition operation
     ///     src0_desc: src0_desc,
     ///     src1_desc: src1_desc,
+    ///     src2_desc: None,
     ///     dst_desc: dst_desc,
     ///     attr: PrimitiveAttributes::new().unwrap(),
     /// };
@@ -180,16 +259,32 @@ impl<'a, D: Direction, P: PropType<D>, C: PrimitiveConfig<'a, D, P>> Primitive<'
         }
     }
 
-    pub fn execute<T>(
+    /// Gets the descriptor of the scratchpad memory, if the primitive needs one. See
+    /// [`PrimitiveDescriptor::scratchpad_desc`].
+    ///
+    /// Unlike `desc`, this keeps working after the first call to
+    /// [`Primitive::execute`].
+    pub fn scratchpad_desc(&self) -> Result<Option<MemoryDescriptor>, DnnlError> {
+        let mut pd = std::ptr::null();
+        let status = unsafe { dnnl_primitive_get_primitive_desc(self.handle, &mut pd) };
+
+        if status == dnnl_status_t::dnnl_success {
+            query_md(pd, dnnl_query_t::dnnl_query_scratchpad_md, 0)
+        } else {
+            Err(status.into())
+        }
+    }
+
+    pub fn execute(
         &mut self,
         stream: &Stream,
-        args: Vec<ExecArg<'_, T>>,
+        args: Vec<ExecArg<'_>>,
     ) -> Result<Option<PrimitiveDescriptor<'a, D, P, C>>, DnnlError> {
         let c_args: Vec<dnnl_exec_arg_t> = args
             .iter()
             .map(|arg| dnnl_exec_arg_t {
                 arg: arg.index,
-                memory: arg.mem.handle,
+                memory: arg.mem.handle(),
             })
             .collect();
 
@@ -210,7 +305,7 @@ impl<'a, D: Direction, P: PropType<D>, C: PrimitiveConfig<'a, D, P>> Primitive<'
     }
 }
 
-impl<'a, D: Direction, P: PropType<D>, C: PrimitiveConfig<'a, D, P>> Drop
+impl<'a, D: Direction, P: Propagation<D>, C: PrimitiveConfig<'a, D, P>> Drop
     for Primitive<'a, D, P, C>
 {
     fn drop(&mut self) {
@@ -220,7 +315,122 @@ impl<'a, D: Direction, P: PropType<D>, C: PrimitiveConfig<'a, D, P>> Drop
     }
 }
 
-pub struct ExecArg<'a, T> {
+/// A memory argument passed to [`Primitive::execute`] under `index`, one of the
+/// `DNNL_ARG_*` values.
+///
+/// The memory is taken as an [`AnyMemory`] so one call can mix data types, e.g. the
+/// `f32` source and `i8` destination of a reorder.
+///
+/// `ExecArg` used to be generic over the element type, as `ExecArg<'a, T>` holding a
+/// `&'a Memory<T>`. Code that names the type now writes `ExecArg<'a>`, and a memory
+/// whose element type was only inferred from the other arguments, e.g. one built from
+/// `AlignedBuffer::zeroed`, needs it spelled out as `AlignedBuffer::<f32>::zeroed`.
+pub struct ExecArg<'a> {
     pub index: i32,
-    pub mem: &'a Memory<T>,
+    pub mem: &'a dyn AnyMemory,
+}
+
+impl<'a> ExecArg<'a> {
+    /// The runtime scales of the memory argument `arg`, passed as
+    /// `DNNL_ARG_ATTR_SCALES | arg`. The attribute needs a scales mask for `arg`, see
+    /// [`PrimitiveAttributes::set_scales_mask`](attributes::PrimitiveAttributes::set_scales_mask).
+    pub fn scales(arg: i32, mem: &'a dyn AnyMemory) -> Self {
+        ExecArg {
+            index: DNNL_ARG_ATTR_SCALES as i32 | arg,
+            mem,
+        }
+    }
+
+    /// The runtime zero points of the memory argument `arg`, passed as
+    /// `DNNL_ARG_ATTR_ZERO_POINTS | arg`. The attribute needs a zero points mask for
+    /// `arg`, see
+    /// [`PrimitiveAttributes::set_zero_points_mask`](attributes::PrimitiveAttributes::set_zero_points_mask).
+    pub fn zero_points(arg: i32, mem: &'a dyn AnyMemory) -> Self {
+        ExecArg {
+            index: DNNL_ARG_ATTR_ZERO_POINTS as i32 | arg,
+            mem,
+        }
+    }
+
+    /// A caller-owned scratchpad, for primitives created with the scratchpad mode set to
+    /// `dnnl_scratchpad_mode_user`. It has to be at least as large as the primitive's
+    /// [`Primitive::scratchpad_desc`].
+    pub fn scratchpad(mem: &'a dyn AnyMemory) -> Self {
+        ExecArg {
+            index: DNNL_ARG_SCRATCHPAD as i32,
+            mem,
+        }
+    }
+
+    /// The `u8` mask written by a fused dropout, see
+    /// [`PrimitiveAttributes::set_dropout`](attributes::PrimitiveAttributes::set_dropout).
+    pub fn dropout_mask(mem: &'a dyn AnyMemory) -> Self {
+        ExecArg {
+            index: DNNL_ARG_ATTR_DROPOUT_MASK as i32,
+            mem,
+        }
+    }
+
+    /// The probability of a fused dropout, a single `f32`.
+    pub fn dropout_probability(mem: &'a dyn AnyMemory) -> Self {
+        ExecArg {
+            index: DNNL_ARG_ATTR_DROPOUT_PROBABILITY as i32,
+            mem,
+        }
+    }
+
+    /// The seed of a fused dropout, a single `s32`.
+    pub fn dropout_seed(mem: &'a dyn AnyMemory) -> Self {
+        ExecArg {
+            index: DNNL_ARG_ATTR_DROPOUT_SEED as i32,
+            mem,
+        }
+    }
+
+    /// The seed of stochastic rounding, a single `s32`, see
+    /// [`PrimitiveAttributes::set_rounding`](attributes::PrimitiveAttributes::set_rounding).
+    pub fn rounding_seed(mem: &'a dyn AnyMemory) -> Self {
+        ExecArg {
+            index: DNNL_ARG_ATTR_ROUNDING_SEED as i32,
+            mem,
+        }
+    }
+}
+
+/// Size one scratchpad for several primitives, from their
+/// [`Primitive::scratchpad_desc`]s.
+///
+/// The result is a byte buffer as large as the largest scratchpad, or `None` if none of
+/// the primitives needs one. A single memory created from it can be passed as
+/// [`ExecArg::scratchpad`] to all of them, as long as they do not run at the same time,
+/// e.g. the layers of a model executed one after another on the same stream.
+///
+/// ```
+/// use onednnl::{
+///     memory::descriptor::{new_plain_descriptor, DataType, DimsQuery},
+///     primitive::shared_scratchpad_desc,
+/// };
+///
+/// let scratchpads = [
+///     Some(new_plain_descriptor(1, vec![100], DataType::U8)),
+///     None,
+///     Some(new_plain_descriptor(1, vec![300], DataType::U8)),
+/// ];
+///
+/// let shared = shared_scratchpad_desc(&scratchpads).unwrap().unwrap();
+///
+/// assert_eq!(shared.query::<DimsQuery>(), Ok(vec![300]));
+/// assert!(shared_scratchpad_desc(&[None]).unwrap().is_none());
+/// ```
+pub fn shared_scratchpad_desc(
+    scratchpads: &[Option<MemoryDescriptor>],
+) -> Result<Option<MemoryDescriptor>, DnnlError> {
+    let size = scratchpads
+        .iter()
+        .flatten()
+        .map(|desc| desc.get_size())
+        .max();
+
+    size.map(|size| MemoryDescriptor::new::<1, a>([size as dnnl_dim_t], DataType::U8))
+        .transpose()
 }
@@ -1,10 +1,22 @@
 use {
-    crate::error::DnnlError,
+    super::post_ops::PostOps,
+    crate::{
+        error::DnnlError,
+        memory::descriptor::{MemoryDescriptor, NDimsQuery},
+    },
     onednnl_sys::{
-        dnnl_accumulation_mode_t, dnnl_primitive_attr_create, dnnl_primitive_attr_destroy,
+        dnnl_accumulation_mode_t, dnnl_data_type_t, dnnl_dim_t, dnnl_fpmath_mode_t,
+        dnnl_memory_desc_clone, dnnl_primitive_attr_create, dnnl_primitive_attr_destroy,
         dnnl_primitive_attr_get_accumulation_mode, dnnl_primitive_attr_get_deterministic,
-        dnnl_primitive_attr_set_accumulation_mode, dnnl_primitive_attr_set_deterministic,
-        dnnl_primitive_attr_t,
+        dnnl_primitive_attr_get_dropout, dnnl_primitive_attr_get_fpmath_mode_v2,
+        dnnl_primitive_attr_get_post_ops, dnnl_primitive_attr_get_rounding,
+        dnnl_primitive_attr_get_scratchpad_mode, dnnl_primitive_attr_set_accumulation_mode,
+        dnnl_primitive_attr_set_deterministic, dnnl_primitive_attr_set_dropout,
+        dnnl_primitive_attr_set_fpmath_mode_v2, dnnl_primitive_attr_set_post_ops,
+        dnnl_primitive_attr_set_rounding, dnnl_primitive_attr_set_scales,
+        dnnl_primitive_attr_set_scales_mask, dnnl_primitive_attr_set_scratchpad_mode,
+        dnnl_primitive_attr_set_zero_points, dnnl_primitive_attr_set_zero_points_mask,
+        dnnl_primitive_attr_t, dnnl_rounding_mode_t, dnnl_scratchpad_mode_t,
         dnnl_status_t::{self},
     },
 };
@@ -154,6 +166,487 @@ impl PrimitiveAttributes {
             Err(status.into())
         }
     }
+
+    /// Get the floating-point math mode, and whether it also applies to integer
+    /// primitives
+    ///
+    /// ```
+    /// use {onednnl::primitive::attributes::PrimitiveAttributes, onednnl_sys::dnnl_fpmath_mode_t};
+    ///
+    /// let attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// assert_eq!(
+    ///     attr.get_fpmath_mode(),
+    ///     Ok((dnnl_fpmath_mode_t::dnnl_fpmath_mode_strict, false))
+    /// );
+    /// ```
+    pub fn get_fpmath_mode(&self) -> Result<(dnnl_fpmath_mode_t::Type, bool), DnnlError> {
+        let mut mode = 0;
+        let mut apply_to_int = 0;
+        let status = unsafe {
+            dnnl_primitive_attr_get_fpmath_mode_v2(self.handle, &mut mode, &mut apply_to_int)
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok((mode, apply_to_int == 1))
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Set the floating-point math mode
+    ///
+    /// Any mode other than `dnnl_fpmath_mode_strict` lets f32 primitives compute
+    /// internally in a lower precision, e.g. `dnnl_fpmath_mode_bf16` or
+    /// `dnnl_fpmath_mode_tf32`. With `apply_to_int`, integer primitives such as a matmul
+    /// with int8 weights may also convert their inputs to that precision.
+    ///
+    /// ```
+    /// use {onednnl::primitive::attributes::PrimitiveAttributes, onednnl_sys::dnnl_fpmath_mode_t};
+    ///
+    /// let mut attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// assert_eq!(
+    ///     attr.set_fpmath_mode(dnnl_fpmath_mode_t::dnnl_fpmath_mode_bf16, true),
+    ///     Ok(())
+    /// );
+    ///
+    /// assert_eq!(
+    ///     attr.get_fpmath_mode(),
+    ///     Ok((dnnl_fpmath_mode_t::dnnl_fpmath_mode_bf16, true))
+    /// );
+    /// ```
+    pub fn set_fpmath_mode(
+        &mut self,
+        mode: dnnl_fpmath_mode_t::Type,
+        apply_to_int: bool,
+    ) -> Result<(), DnnlError> {
+        let status = unsafe {
+            dnnl_primitive_attr_set_fpmath_mode_v2(self.handle, mode, apply_to_int as i32)
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(())
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Get the rounding mode used when writing the `arg` memory argument
+    ///
+    /// ```
+    /// use {
+    ///     onednnl::primitive::attributes::PrimitiveAttributes,
+    ///     onednnl_sys::{dnnl_rounding_mode_t, DNNL_ARG_DST},
+    /// };
+    ///
+    /// let attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// assert_eq!(
+    ///     attr.get_rounding(DNNL_ARG_DST as i32),
+    ///     Ok(dnnl_rounding_mode_t::dnnl_rounding_mode_environment)
+    /// );
+    /// ```
+    pub fn get_rounding(&self, arg: i32) -> Result<dnnl_rounding_mode_t::Type, DnnlError> {
+        let mut output = 0;
+        let status = unsafe { dnnl_primitive_attr_get_rounding(self.handle, arg, &mut output) };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(output)
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Set the rounding mode used when writing the `arg` memory argument
+    ///
+    /// `dnnl_rounding_mode_stochastic` rounds randomly in proportion to the distance to
+    /// the neighbouring values. Its seed is passed at execution time as
+    /// [`ExecArg::rounding_seed`](super::ExecArg::rounding_seed).
+    ///
+    /// ```
+    /// use {
+    ///     onednnl::primitive::attributes::PrimitiveAttributes,
+    ///     onednnl_sys::{dnnl_rounding_mode_t, DNNL_ARG_DST},
+    /// };
+    ///
+    /// let mut attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// assert_eq!(
+    ///     attr.set_rounding(
+    ///         DNNL_ARG_DST as i32,
+    ///         dnnl_rounding_mode_t::dnnl_rounding_mode_stochastic
+    ///     ),
+    ///     Ok(())
+    /// );
+    ///
+    /// assert_eq!(
+    ///     attr.get_rounding(DNNL_ARG_DST as i32),
+    ///     Ok(dnnl_rounding_mode_t::dnnl_rounding_mode_stochastic)
+    /// );
+    /// ```
+    pub fn set_rounding(
+        &mut self,
+        arg: i32,
+        mode: dnnl_rounding_mode_t::Type,
+    ) -> Result<(), DnnlError> {
+        let status = unsafe { dnnl_primitive_attr_set_rounding(self.handle, arg, mode) };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(())
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Get the scratchpad mode
+    ///
+    /// ```
+    /// use {
+    ///     onednnl::primitive::attributes::PrimitiveAttributes, onednnl_sys::dnnl_scratchpad_mode_t,
+    /// };
+    ///
+    /// let attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// assert_eq!(
+    ///     attr.get_scratchpad_mode(),
+    ///     Ok(dnnl_scratchpad_mode_t::dnnl_scratchpad_mode_library)
+    /// );
+    /// ```
+    pub fn get_scratchpad_mode(&self) -> Result<dnnl_scratchpad_mode_t::Type, DnnlError> {
+        let mut output = 0;
+        let status = unsafe { dnnl_primitive_attr_get_scratchpad_mode(self.handle, &mut output) };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(output)
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Set the scratchpad mode
+    ///
+    /// By default each primitive allocates its own temporary memory. With
+    /// `dnnl_scratchpad_mode_user` it is passed in instead as `DNNL_ARG_SCRATCHPAD`.
+    ///
+    /// ```
+    /// use {
+    ///     onednnl::primitive::attributes::PrimitiveAttributes, onednnl_sys::dnnl_scratchpad_mode_t,
+    /// };
+    ///
+    /// let mut attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// assert_eq!(
+    ///     attr.set_scratchpad_mode(dnnl_scratchpad_mode_t::dnnl_scratchpad_mode_user),
+    ///     Ok(())
+    /// );
+    ///
+    /// assert_eq!(
+    ///     attr.get_scratchpad_mode(),
+    ///     Ok(dnnl_scratchpad_mode_t::dnnl_scratchpad_mode_user)
+    /// );
+    /// ```
+    pub fn set_scratchpad_mode(
+        &mut self,
+        mode: dnnl_scratchpad_mode_t::Type,
+    ) -> Result<(), DnnlError> {
+        let status = unsafe { dnnl_primitive_attr_set_scratchpad_mode(self.handle, mode) };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(())
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Set the scales mask for the `arg` memory argument, a `DNNL_ARG_*` value.
+    ///
+    /// The scales themselves are passed at execution time as
+    /// `DNNL_ARG_ATTR_SCALES | arg`. A `mask` of 0 uses one scale for the whole
+    /// tensor, and setting bit `d` uses a separate scale along dimension `d`.
+    ///
+    /// ```
+    /// use {onednnl::primitive::attributes::PrimitiveAttributes, onednnl_sys::DNNL_ARG_DST};
+    ///
+    /// let mut attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// assert_eq!(attr.set_scales_mask(DNNL_ARG_DST as i32, 0), Ok(()));
+    /// ```
+    pub fn set_scales_mask(&mut self, arg: i32, mask: i32) -> Result<(), DnnlError> {
+        let status = unsafe { dnnl_primitive_attr_set_scales_mask(self.handle, arg, mask) };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(())
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Set grouped scales for the `arg` memory argument.
+    ///
+    /// Like [`PrimitiveAttributes::set_scales_mask`], but along the dimensions set in
+    /// `mask` every `group_dims` consecutive values share one scale, and the scales are
+    /// stored as `data_type`. `group_dims` has an entry per dimension of the argument,
+    /// and an empty `group_dims` means groups of one.
+    ///
+    /// ```
+    /// use {
+    ///     onednnl::{memory::descriptor::DataType, primitive::attributes::PrimitiveAttributes},
+    ///     onednnl_sys::DNNL_ARG_WEIGHTS,
+    /// };
+    ///
+    /// let mut attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// // One scale per 32 rows of each column of a [K, N] weight matrix.
+    /// assert_eq!(
+    ///     attr.set_scales(
+    ///         DNNL_ARG_WEIGHTS as i32,
+    ///         (1 << 0) | (1 << 1),
+    ///         &[32, 1],
+    ///         DataType::F32
+    ///     ),
+    ///     Ok(())
+    /// );
+    /// ```
+    pub fn set_scales(
+        &mut self,
+        arg: i32,
+        mask: i32,
+        group_dims: &[dnnl_dim_t],
+        data_type: dnnl_data_type_t::Type,
+    ) -> Result<(), DnnlError> {
+        let status = unsafe {
+            dnnl_primitive_attr_set_scales(
+                self.handle,
+                arg,
+                mask,
+                group_dims.len() as i32,
+                if group_dims.is_empty() {
+                    std::ptr::null()
+                } else {
+                    group_dims.as_ptr()
+                },
+                data_type,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(())
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Set the zero points mask for the `arg` memory argument, a `DNNL_ARG_*` value.
+    ///
+    /// The zero points are passed at execution time as
+    /// `DNNL_ARG_ATTR_ZERO_POINTS | arg`, as `s32` values. The mask works as for
+    /// [`PrimitiveAttributes::set_scales_mask`].
+    ///
+    /// ```
+    /// use {onednnl::primitive::attributes::PrimitiveAttributes, onednnl_sys::DNNL_ARG_SRC};
+    ///
+    /// let mut attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// assert_eq!(attr.set_zero_points_mask(DNNL_ARG_SRC as i32, 0), Ok(()));
+    /// ```
+    pub fn set_zero_points_mask(&mut self, arg: i32, mask: i32) -> Result<(), DnnlError> {
+        let status = unsafe { dnnl_primitive_attr_set_zero_points_mask(self.handle, arg, mask) };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(())
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Set grouped zero points for the `arg` memory argument, stored as `data_type`.
+    /// `group_dims` works as for [`PrimitiveAttributes::set_scales`].
+    ///
+    /// ```
+    /// use {
+    ///     onednnl::{memory::descriptor::DataType, primitive::attributes::PrimitiveAttributes},
+    ///     onednnl_sys::DNNL_ARG_SRC,
+    /// };
+    ///
+    /// let mut attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// assert_eq!(
+    ///     attr.set_zero_points(DNNL_ARG_SRC as i32, 0, &[], DataType::S32),
+    ///     Ok(())
+    /// );
+    /// ```
+    pub fn set_zero_points(
+        &mut self,
+        arg: i32,
+        mask: i32,
+        group_dims: &[dnnl_dim_t],
+        data_type: dnnl_data_type_t::Type,
+    ) -> Result<(), DnnlError> {
+        let status = unsafe {
+            dnnl_primitive_attr_set_zero_points(
+                self.handle,
+                arg,
+                mask,
+                group_dims.len() as i32,
+                if group_dims.is_empty() {
+                    std::ptr::null()
+                } else {
+                    group_dims.as_ptr()
+                },
+                data_type,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(())
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Set the post-ops fused onto the primitive. The attribute keeps its own copy, so
+    /// `post_ops` can be dropped or changed afterwards.
+    ///
+    /// ```
+    /// use onednnl::{
+    ///     primitive::{attributes::PrimitiveAttributes, post_ops::PostOps},
+    ///     primitives::eltwise::Unary,
+    /// };
+    ///
+    /// let mut attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// let mut post_ops = PostOps::new().unwrap();
+    /// post_ops.append_eltwise(Unary::RELU, 0.0, 0.0).unwrap();
+    ///
+    /// assert_eq!(attr.set_post_ops(&post_ops), Ok(()));
+    /// ```
+    pub fn set_post_ops(&mut self, post_ops: &PostOps) -> Result<(), DnnlError> {
+        let status = unsafe { dnnl_primitive_attr_set_post_ops(self.handle, post_ops.handle) };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(())
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Get a copy of the post-ops
+    ///
+    /// ```
+    /// use onednnl::{
+    ///     primitive::{attributes::PrimitiveAttributes, post_ops::PostOps},
+    ///     primitives::eltwise::Unary,
+    /// };
+    ///
+    /// let mut attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// assert!(attr.get_post_ops().unwrap().is_empty());
+    ///
+    /// let mut post_ops = PostOps::new().unwrap();
+    /// post_ops.append_eltwise(Unary::RELU, 0.0, 0.0).unwrap();
+    /// post_ops.append_sum(1.0, 0, 0).unwrap();
+    ///
+    /// attr.set_post_ops(&post_ops).unwrap();
+    ///
+    /// assert_eq!(attr.get_post_ops().unwrap().len(), 2);
+    /// ```
+    pub fn get_post_ops(&self) -> Result<PostOps, DnnlError> {
+        let mut handle = std::ptr::null();
+        let status = unsafe { dnnl_primitive_attr_get_post_ops(self.handle, &mut handle) };
+
+        if status == dnnl_status_t::dnnl_success {
+            PostOps::clone_from_handle(handle)
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Fuse dropout onto the end of the primitive, writing which values were kept to a
+    /// mask described by `mask_desc`.
+    ///
+    /// `mask_desc` has the shape of the destination and the `u8` data type. The dropout
+    /// probability, as a single `f32`, and the seed, as a single `s32`, are passed at
+    /// execution time with [`ExecArg::dropout_probability`] and [`ExecArg::dropout_seed`],
+    /// and the mask with [`ExecArg::dropout_mask`]. Kept values are scaled by
+    /// `1 / (1 - probability)`, and the same seed drops the same values.
+    ///
+    /// ```
+    /// use onednnl::{
+    ///     memory::descriptor::{new_plain_descriptor, DataType},
+    ///     primitive::attributes::PrimitiveAttributes,
+    /// };
+    ///
+    /// let mut attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// let mask_desc = new_plain_descriptor(2, vec![4, 8], DataType::U8);
+    ///
+    /// assert_eq!(attr.set_dropout(&mask_desc), Ok(()));
+    /// ```
+    ///
+    /// [`ExecArg::dropout_probability`]: super::ExecArg::dropout_probability
+    /// [`ExecArg::dropout_seed`]: super::ExecArg::dropout_seed
+    /// [`ExecArg::dropout_mask`]: super::ExecArg::dropout_mask
+    pub fn set_dropout(&mut self, mask_desc: &MemoryDescriptor) -> Result<(), DnnlError> {
+        let status = unsafe { dnnl_primitive_attr_set_dropout(self.handle, mask_desc.handle) };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(())
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Get the dropout mask descriptor, or `None` if dropout is not set
+    ///
+    /// ```
+    /// use onednnl::{
+    ///     memory::descriptor::{new_plain_descriptor, DataType, DimsQuery},
+    ///     primitive::attributes::PrimitiveAttributes,
+    /// };
+    ///
+    /// let mut attr = PrimitiveAttributes::new().unwrap();
+    ///
+    /// assert!(attr.get_dropout().unwrap().is_none());
+    ///
+    /// attr.set_dropout(&new_plain_descriptor(2, vec![4, 8], DataType::U8))
+    ///     .unwrap();
+    ///
+    /// let mask_desc = attr.get_dropout().unwrap().unwrap();
+    ///
+    /// assert_eq!(mask_desc.query::<DimsQuery>(), Ok(vec![4, 8]));
+    /// ```
+    pub fn get_dropout(&self) -> Result<Option<MemoryDescriptor>, DnnlError> {
+        let mut md = std::ptr::null();
+        let status = unsafe { dnnl_primitive_attr_get_dropout(self.handle, &mut md) };
+
+        if status != dnnl_status_t::dnnl_success {
+            return Err(status.into());
+        }
+
+        if md.is_null() {
+            return Ok(None);
+        }
+
+        // The descriptor belongs to the attribute, so hand out a copy.
+        let mut cloned_handle = std::ptr::null_mut();
+        let status = unsafe { dnnl_memory_desc_clone(&mut cloned_handle, md) };
+
+        if status != dnnl_status_t::dnnl_success {
+            return Err(status.into());
+        }
+
+        let desc = MemoryDescriptor {
+            handle: cloned_handle,
+        };
+
+        // Without dropout, oneDNN answers with a zero memory descriptor.
+        if desc.query::<NDimsQuery>()? == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(desc))
+        }
+    }
 }
 
 impl Drop for PrimitiveAttributes {
@@ -1,10 +1,10 @@
 use {
-    super::{descriptor::PrimitiveDescriptor, Direction, PropType},
+    super::{descriptor::PrimitiveDescriptor, Direction, Propagation},
     crate::{engine::Engine, error::DnnlError},
     std::sync::Arc,
 };
 
-pub trait PrimitiveConfig<'a, D: Direction, P: PropType<D>>: Sized {
+pub trait PrimitiveConfig<'a, D: Direction, P: Propagation<D>>: Sized {
     fn create_primitive_desc(
         self,
         engine: Arc<Engine>,
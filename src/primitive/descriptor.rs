@@ -1,14 +1,21 @@
 use {
-    super::{config::PrimitiveConfig, Direction, Operation, PropType},
-    crate::{engine::Engine, error::DnnlError},
-    onednnl_sys::{dnnl_primitive_desc_destroy, dnnl_primitive_desc_t},
+    super::{config::PrimitiveConfig, Direction, Operation, Propagation},
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::{MemoryDescriptor, NDimsQuery},
+    },
+    onednnl_sys::{
+        const_dnnl_primitive_desc_t, dnnl_memory_desc_clone, dnnl_primitive_desc_destroy,
+        dnnl_primitive_desc_query_md, dnnl_primitive_desc_t, dnnl_query_t, dnnl_status_t,
+    },
     std::{marker::PhantomData, sync::Arc},
 };
 
 pub struct PrimitiveDescriptor<
     'a,
     D: Direction,
-    P: PropType<D>,
+    P: Propagation<D>,
     C: PrimitiveConfig<'a, D, P> + Sized,
 > {
     pub handle: dnnl_primitive_desc_t,
@@ -19,7 +26,7 @@ pub struct PrimitiveDescriptor<
     pub(crate) _marker_p: PhantomData<P>,
 }
 
-impl<'a, D: Direction, P: PropType<D>, C: PrimitiveConfig<'a, D, P> + Sized>
+impl<'a, D: Direction, P: Propagation<D>, C: PrimitiveConfig<'a, D, P> + Sized>
     PrimitiveDescriptor<'a, D, P, C>
 {
     /// Creates a new `PrimitiveDescriptor`.
@@ -51,6 +58,7 @@ impl<'a, D: Direction, P: PropType<D>, C: PrimitiveConfig<'a, D, P> + Sized>
     ///     alg_kind: dnnl_alg_kind_t::dnnl_binary_add, // Example: addition operation
     ///     src0_desc: src0_desc,
     ///     src1_desc: src1_desc,
+    ///     src2_desc: None,
     ///     dst_desc: dst_desc,
     ///     attr: PrimitiveAttributes::new().unwrap(),
     /// };
@@ -68,12 +76,113 @@ impl<'a, D: Direction, P: PropType<D>, C: PrimitiveConfig<'a, D, P> + Sized>
     ) -> Result<PrimitiveDescriptor<'a, D, P, C>, DnnlError> {
         config.create_primitive_desc(engine)
     }
+
+    /// Queries a memory descriptor, such as `dnnl_query_dst_md`, from the primitive
+    /// descriptor.
+    ///
+    /// Returns `None` when the primitive does not use the queried memory.
+    ///
+    /// ```
+    /// use {
+    ///     onednnl::{
+    ///         engine::Engine,
+    ///         memory::{descriptor::MemoryDescriptor, format_tag::x},
+    ///         primitive::{
+    ///             attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor,
+    ///             PropForwardInference,
+    ///         },
+    ///         primitives::binary::{Binary, ForwardBinary, ForwardBinaryConfig},
+    ///     },
+    ///     onednnl_sys::{dnnl_data_type_t::dnnl_f32, dnnl_query_t},
+    /// };
+    ///
+    /// let engine = Engine::new(Engine::CPU, 0).unwrap();
+    ///
+    /// let binary_config = ForwardBinaryConfig {
+    ///     alg_kind: Binary::ADD,
+    ///     src0_desc: MemoryDescriptor::new::<1, x>([15], dnnl_f32).unwrap(),
+    ///     src1_desc: MemoryDescriptor::new::<1, x>([15], dnnl_f32).unwrap(),
+    ///     src2_desc: None,
+    ///     dst_desc: MemoryDescriptor::new::<1, x>([15], dnnl_f32).unwrap(),
+    ///     attr: PrimitiveAttributes::new().unwrap(),
+    /// };
+    ///
+    /// let primitive_descriptor = PrimitiveDescriptor::<_, _, ForwardBinaryConfig>::new::<
+    ///     ForwardBinary<PropForwardInference>,
+    /// >(binary_config, engine)
+    /// .unwrap();
+    ///
+    /// let dst_desc = primitive_descriptor.query_md(dnnl_query_t::dnnl_query_dst_md, 0);
+    ///
+    /// assert_eq!(
+    ///     dst_desc,
+    ///     Ok(Some(MemoryDescriptor::new::<1, x>([15], dnnl_f32).unwrap()))
+    /// );
+    ///
+    /// assert_eq!(primitive_descriptor.workspace_desc(), Ok(None));
+    /// ```
+    pub fn query_md(
+        &self,
+        what: dnnl_query_t::Type,
+        index: i32,
+    ) -> Result<Option<MemoryDescriptor>, DnnlError> {
+        query_md(self.handle, what, index)
+    }
+
+    /// Gets the descriptor of the workspace memory, if the primitive needs one.
+    ///
+    /// Primitives such as max pooling or LRN, when created for forward training, write
+    /// a workspace that the matching backward primitive reads. Pass a memory created
+    /// from this descriptor as `DNNL_ARG_WORKSPACE` to both.
+    pub fn workspace_desc(&self) -> Result<Option<MemoryDescriptor>, DnnlError> {
+        self.query_md(dnnl_query_t::dnnl_query_workspace_md, 0)
+    }
+
+    /// Gets the descriptor of the scratchpad memory, if the primitive needs one.
+    ///
+    /// The scratchpad is temporary memory used while the primitive runs. oneDNN
+    /// allocates it unless the attribute's scratchpad mode is
+    /// `dnnl_scratchpad_mode_user`, in which case pass a memory created from this
+    /// descriptor as `DNNL_ARG_SCRATCHPAD`.
+    pub fn scratchpad_desc(&self) -> Result<Option<MemoryDescriptor>, DnnlError> {
+        self.query_md(dnnl_query_t::dnnl_query_scratchpad_md, 0)
+    }
 }
 
-impl<'a, D: Direction, P: PropType<D>, C: PrimitiveConfig<'a, D, P>> Drop
+impl<'a, D: Direction, P: Propagation<D>, C: PrimitiveConfig<'a, D, P>> Drop
     for PrimitiveDescriptor<'a, D, P, C>
 {
     fn drop(&mut self) {
         unsafe { dnnl_primitive_desc_destroy(self.handle) };
     }
 }
+
+pub(crate) fn query_md(
+    handle: const_dnnl_primitive_desc_t,
+    what: dnnl_query_t::Type,
+    index: i32,
+) -> Result<Option<MemoryDescriptor>, DnnlError> {
+    let md = unsafe { dnnl_primitive_desc_query_md(handle, what, index) };
+
+    if md.is_null() {
+        return Ok(None);
+    }
+
+    let mut cloned_handle = std::ptr::null_mut();
+    let status = unsafe { dnnl_memory_desc_clone(&mut cloned_handle, md) };
+
+    if status != dnnl_status_t::dnnl_success {
+        return Err(status.into());
+    }
+
+    let desc = MemoryDescriptor {
+        handle: cloned_handle,
+    };
+
+    // oneDNN answers with a zero memory descriptor for memories the primitive does not use.
+    if desc.query::<NDimsQuery>()? == 0 {
+        Ok(None)
+    } else {
+        Ok(Some(desc))
+    }
+}
@@ -0,0 +1,316 @@
+use {
+    crate::{error::DnnlError, memory::descriptor::MemoryDescriptor},
+    onednnl_sys::{
+        const_dnnl_post_ops_t, dnnl_alg_kind_t, dnnl_data_type_t, dnnl_dim_t,
+        dnnl_memory_desc_clone, dnnl_post_ops_append_binary, dnnl_post_ops_append_dw,
+        dnnl_post_ops_append_eltwise, dnnl_post_ops_append_prelu, dnnl_post_ops_append_sum,
+        dnnl_post_ops_clone, dnnl_post_ops_create, dnnl_post_ops_destroy, dnnl_post_ops_get_kind,
+        dnnl_post_ops_get_params_binary, dnnl_post_ops_get_params_dw,
+        dnnl_post_ops_get_params_eltwise, dnnl_post_ops_get_params_prelu,
+        dnnl_post_ops_get_params_sum, dnnl_post_ops_len, dnnl_post_ops_t, dnnl_primitive_kind_t,
+        dnnl_status_t, DNNL_ARG_ATTR_MULTIPLE_POST_OP_BASE,
+    },
+};
+
+/// A chain of operations fused onto the end of a primitive, applied in the order they
+/// were appended. Attach it with
+/// [`PrimitiveAttributes::set_post_ops`](super::attributes::PrimitiveAttributes::set_post_ops).
+///
+/// Post-ops that read their own memory get it at execution time under
+/// [`PostOps::arg`]: `PostOps::arg(index, DNNL_ARG_SRC_1 as i32)` for a binary post-op and
+/// `PostOps::arg(index, DNNL_ARG_WEIGHTS as i32)` for PReLU slopes, where `index` is the
+/// position of the post-op in the chain.
+pub struct PostOps {
+    pub(crate) handle: dnnl_post_ops_t,
+}
+
+/// One entry of a [`PostOps`] chain, as read back by [`PostOps::get`].
+#[derive(Debug)]
+pub enum PostOp {
+    Eltwise {
+        alg_kind: dnnl_alg_kind_t::Type,
+        alpha: f32,
+        beta: f32,
+    },
+    Sum {
+        scale: f32,
+        zero_point: i32,
+        data_type: dnnl_data_type_t::Type,
+    },
+    Binary {
+        alg_kind: dnnl_alg_kind_t::Type,
+        src1_desc: MemoryDescriptor,
+    },
+    Prelu {
+        mask: i32,
+    },
+    Dw {
+        weights_data_type: dnnl_data_type_t::Type,
+        bias_data_type: dnnl_data_type_t::Type,
+        dst_data_type: dnnl_data_type_t::Type,
+        kernel_size: dnnl_dim_t,
+        stride_size: dnnl_dim_t,
+        padding_l_size: dnnl_dim_t,
+    },
+}
+
+fn check(status: dnnl_status_t::Type) -> Result<(), DnnlError> {
+    if status == dnnl_status_t::dnnl_success {
+        Ok(())
+    } else {
+        Err(status.into())
+    }
+}
+
+impl PostOps {
+    /// Create an empty chain of post-ops
+    ///
+    /// ```
+    /// use onednnl::primitive::post_ops::PostOps;
+    ///
+    /// let post_ops = PostOps::new().unwrap();
+    ///
+    /// assert!(post_ops.is_empty());
+    /// ```
+    pub fn new() -> Result<Self, DnnlError> {
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe { dnnl_post_ops_create(&mut handle) };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(Self { handle })
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// Copy a chain owned by oneDNN, e.g. the one held by a primitive attribute.
+    pub(crate) fn clone_from_handle(existing: const_dnnl_post_ops_t) -> Result<Self, DnnlError> {
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe { dnnl_post_ops_clone(&mut handle, existing) };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(Self { handle })
+        } else {
+            Err(status.into())
+        }
+    }
+
+    /// The execution argument index of the memory `arg` for the post-op at `index`.
+    ///
+    /// ```
+    /// use onednnl::{
+    ///     onednnl_sys::{DNNL_ARG_ATTR_MULTIPLE_POST_OP_BASE, DNNL_ARG_SRC_1},
+    ///     primitive::post_ops::PostOps,
+    /// };
+    ///
+    /// assert_eq!(
+    ///     PostOps::arg(0, DNNL_ARG_SRC_1 as i32),
+    ///     (DNNL_ARG_ATTR_MULTIPLE_POST_OP_BASE | DNNL_ARG_SRC_1) as i32
+    /// );
+    /// ```
+    pub fn arg(index: usize, arg: i32) -> i32 {
+        (DNNL_ARG_ATTR_MULTIPLE_POST_OP_BASE as i32 * (index as i32 + 1)) | arg
+    }
+
+    /// Apply an elementwise operation, one of the [`Unary`](crate::primitives::eltwise::Unary)
+    /// algorithms, to the result.
+    pub fn append_eltwise(
+        &mut self,
+        alg_kind: dnnl_alg_kind_t::Type,
+        alpha: f32,
+        beta: f32,
+    ) -> Result<(), DnnlError> {
+        check(unsafe { dnnl_post_ops_append_eltwise(self.handle, alg_kind, alpha, beta) })
+    }
+
+    /// Add the result to what is already in the destination, after multiplying the old
+    /// destination by `scale` and shifting it by `zero_point`. `data_type` reinterprets
+    /// the old destination, or is `dnnl_data_type_undef` to keep the destination's own.
+    pub fn append_sum(
+        &mut self,
+        scale: f32,
+        zero_point: i32,
+        data_type: dnnl_data_type_t::Type,
+    ) -> Result<(), DnnlError> {
+        check(unsafe { dnnl_post_ops_append_sum(self.handle, scale, zero_point, data_type) })
+    }
+
+    /// Combine the result with a second source, described by `src1_desc`, using one of
+    /// the [`Binary`](crate::primitives::binary::Binary) algorithms. `src1_desc` may
+    /// broadcast over the destination.
+    pub fn append_binary(
+        &mut self,
+        alg_kind: dnnl_alg_kind_t::Type,
+        src1_desc: &MemoryDescriptor,
+    ) -> Result<(), DnnlError> {
+        check(unsafe { dnnl_post_ops_append_binary(self.handle, alg_kind, src1_desc.handle) })
+    }
+
+    /// Apply a PReLU to the result, with slopes broadcast over the dimensions not set in
+    /// `mask`.
+    pub fn append_prelu(&mut self, mask: i32) -> Result<(), DnnlError> {
+        check(unsafe { dnnl_post_ops_append_prelu(self.handle, mask) })
+    }
+
+    /// Follow a 1x1 convolution with a depthwise convolution. Its weights and bias are
+    /// passed as `DNNL_ARG_ATTR_POST_OP_DW | DNNL_ARG_WEIGHTS` and
+    /// `DNNL_ARG_ATTR_POST_OP_DW | DNNL_ARG_BIAS`.
+    pub fn append_dw(
+        &mut self,
+        weights_data_type: dnnl_data_type_t::Type,
+        bias_data_type: dnnl_data_type_t::Type,
+        dst_data_type: dnnl_data_type_t::Type,
+        kernel_size: dnnl_dim_t,
+        stride_size: dnnl_dim_t,
+        padding_l_size: dnnl_dim_t,
+    ) -> Result<(), DnnlError> {
+        check(unsafe {
+            dnnl_post_ops_append_dw(
+                self.handle,
+                weights_data_type,
+                bias_data_type,
+                dst_data_type,
+                kernel_size,
+                stride_size,
+                padding_l_size,
+            )
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { dnnl_post_ops_len(self.handle) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Read back the post-op at `index`
+    ///
+    /// ```
+    /// use onednnl::{
+    ///     primitive::post_ops::{PostOp, PostOps},
+    ///     primitives::eltwise::Unary,
+    /// };
+    ///
+    /// let mut post_ops = PostOps::new().unwrap();
+    ///
+    /// post_ops.append_eltwise(Unary::RELU, 0.0, 0.0).unwrap();
+    /// post_ops.append_prelu(2).unwrap();
+    ///
+    /// assert_eq!(post_ops.len(), 2);
+    /// assert!(matches!(
+    ///     post_ops.get(0),
+    ///     Ok(PostOp::Eltwise {
+    ///         alg_kind: Unary::RELU,
+    ///         ..
+    ///     })
+    /// ));
+    /// assert!(matches!(post_ops.get(1), Ok(PostOp::Prelu { mask: 2 })));
+    /// assert!(post_ops.get(2).is_err());
+    /// ```
+    pub fn get(&self, index: usize) -> Result<PostOp, DnnlError> {
+        if index >= self.len() {
+            return Err(DnnlError::InvalidArguments);
+        }
+
+        let index = index as i32;
+
+        match unsafe { dnnl_post_ops_get_kind(self.handle, index) } {
+            dnnl_primitive_kind_t::dnnl_eltwise => {
+                let (mut alg_kind, mut alpha, mut beta) = (0, 0.0, 0.0);
+                check(unsafe {
+                    dnnl_post_ops_get_params_eltwise(
+                        self.handle,
+                        index,
+                        &mut alg_kind,
+                        &mut alpha,
+                        &mut beta,
+                    )
+                })?;
+
+                Ok(PostOp::Eltwise {
+                    alg_kind,
+                    alpha,
+                    beta,
+                })
+            }
+            dnnl_primitive_kind_t::dnnl_sum => {
+                let (mut scale, mut zero_point, mut data_type) = (0.0, 0, 0);
+                check(unsafe {
+                    dnnl_post_ops_get_params_sum(
+                        self.handle,
+                        index,
+                        &mut scale,
+                        &mut zero_point,
+                        &mut data_type,
+                    )
+                })?;
+
+                Ok(PostOp::Sum {
+                    scale,
+                    zero_point,
+                    data_type,
+                })
+            }
+            dnnl_primitive_kind_t::dnnl_binary => {
+                let mut alg_kind = 0;
+                let mut md = std::ptr::null();
+                check(unsafe {
+                    dnnl_post_ops_get_params_binary(self.handle, index, &mut alg_kind, &mut md)
+                })?;
+
+                // The descriptor belongs to the post-ops, so hand out a copy.
+                let mut cloned_handle = std::ptr::null_mut();
+                check(unsafe { dnnl_memory_desc_clone(&mut cloned_handle, md) })?;
+
+                Ok(PostOp::Binary {
+                    alg_kind,
+                    src1_desc: MemoryDescriptor {
+                        handle: cloned_handle,
+                    },
+                })
+            }
+            dnnl_primitive_kind_t::dnnl_prelu => {
+                let mut mask = 0;
+                check(unsafe { dnnl_post_ops_get_params_prelu(self.handle, index, &mut mask) })?;
+
+                Ok(PostOp::Prelu { mask })
+            }
+            dnnl_primitive_kind_t::dnnl_convolution => {
+                let (mut weights_data_type, mut bias_data_type, mut dst_data_type) = (0, 0, 0);
+                let (mut kernel_size, mut stride_size, mut padding_l_size) = (0, 0, 0);
+                check(unsafe {
+                    dnnl_post_ops_get_params_dw(
+                        self.handle,
+                        index,
+                        &mut weights_data_type,
+                        &mut bias_data_type,
+                        &mut dst_data_type,
+                        &mut kernel_size,
+                        &mut stride_size,
+                        &mut padding_l_size,
+                    )
+                })?;
+
+                Ok(PostOp::Dw {
+                    weights_data_type,
+                    bias_data_type,
+                    dst_data_type,
+                    kernel_size,
+                    stride_size,
+                    padding_l_size,
+                })
+            }
+            _ => Err(DnnlError::Unsupported),
+        }
+    }
+}
+
+impl Drop for PostOps {
+    fn drop(&mut self) {
+        unsafe {
+            dnnl_post_ops_destroy(self.handle);
+        }
+    }
+}
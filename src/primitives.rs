@@ -1,8 +1,110 @@
+use {
+    crate::{
+        error::DnnlError,
+        memory::descriptor::{MemoryDescriptor, NDimsQuery},
+    },
+    onednnl_sys::{dnnl_dim_t, dnnl_normalization_flags_t},
+    std::{
+        ffi::c_uint,
+        ops::{BitOr, BitOrAssign},
+    },
+};
+
 pub mod au_gru;
 pub mod batch_norm;
 pub mod binary;
+pub mod concat;
+pub mod convolution;
+pub mod deconvolution;
 pub mod eltwise;
+pub mod group_norm;
+pub mod gru;
 pub mod inner_product;
+pub mod layer_norm;
+pub mod lbr_augru;
+pub mod lbr_gru;
+pub mod lrn;
+pub mod lstm;
 pub mod matmul;
+pub mod pooling;
 pub mod prelu;
 pub mod reduction;
+pub mod reorder;
+pub mod resampling;
+pub mod rnn;
+pub mod shuffle;
+pub mod softmax;
+pub mod sum;
+pub mod vanilla_rnn;
+
+/// oneDNN reads strides, kernels, dilations and paddings as arrays with one entry per
+/// spatial dimension, so check their lengths against `src_desc` before handing them over.
+pub(crate) fn check_spatial_dims(
+    src_desc: &MemoryDescriptor,
+    params: &[&[dnnl_dim_t]],
+) -> Result<(), DnnlError> {
+    let spatial_dims = (src_desc.query::<NDimsQuery>()? - 2).max(0) as usize;
+
+    if params.iter().all(|p| p.len() == spatial_dims) {
+        Ok(())
+    } else {
+        Err(DnnlError::InvalidArguments)
+    }
+}
+
+/// Flags for the normalization primitives, combined with `|`.
+///
+/// ```
+/// use onednnl::primitives::NormalizationFlags;
+///
+/// let flags = NormalizationFlags::USE_SCALE | NormalizationFlags::USE_SHIFT;
+///
+/// assert!(flags.contains(NormalizationFlags::USE_SHIFT));
+/// assert!(!flags.contains(NormalizationFlags::USE_GLOBAL_STATS));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NormalizationFlags(c_uint);
+
+impl NormalizationFlags {
+    pub const NONE: NormalizationFlags =
+        NormalizationFlags(dnnl_normalization_flags_t::dnnl_normalization_flags_none);
+    /// Use the mean and variance passed in as `DNNL_ARG_MEAN` and `DNNL_ARG_VARIANCE`
+    /// instead of computing them.
+    pub const USE_GLOBAL_STATS: NormalizationFlags =
+        NormalizationFlags(dnnl_normalization_flags_t::dnnl_use_global_stats);
+    /// Multiply by `DNNL_ARG_SCALE` after normalizing.
+    pub const USE_SCALE: NormalizationFlags =
+        NormalizationFlags(dnnl_normalization_flags_t::dnnl_use_scale);
+    /// Add `DNNL_ARG_SHIFT` after normalizing.
+    pub const USE_SHIFT: NormalizationFlags =
+        NormalizationFlags(dnnl_normalization_flags_t::dnnl_use_shift);
+    /// Apply a ReLU to the result. Batch normalization only; for training the forward
+    /// primitive writes a workspace that the backward primitive needs.
+    pub const FUSE_NORM_RELU: NormalizationFlags =
+        NormalizationFlags(dnnl_normalization_flags_t::dnnl_fuse_norm_relu);
+    /// Add `DNNL_ARG_SRC_1` and apply a ReLU to the result. Batch normalization only.
+    pub const FUSE_NORM_ADD_RELU: NormalizationFlags =
+        NormalizationFlags(dnnl_normalization_flags_t::dnnl_fuse_norm_add_relu);
+
+    pub fn contains(self, other: NormalizationFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn bits(self) -> c_uint {
+        self.0
+    }
+}
+
+impl BitOr for NormalizationFlags {
+    type Output = NormalizationFlags;
+
+    fn bitor(self, rhs: NormalizationFlags) -> NormalizationFlags {
+        NormalizationFlags(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for NormalizationFlags {
+    fn bitor_assign(&mut self, rhs: NormalizationFlags) {
+        self.0 |= rhs.0;
+    }
+}
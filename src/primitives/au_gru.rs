@@ -1,162 +1,811 @@
 use {
-    crate::{
-        engine::Engine,
-        error::DnnlError,
-        memory::descriptor::MemoryDescriptor,
-        primitive::{
-            attributes::PrimitiveAttributes, config::PrimitiveConfig,
-            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
-            PropForwardTraining, PropType,
-        },
-    },
-    onednnl_sys::{
-        dnnl_augru_backward_primitive_desc_create, dnnl_augru_forward_primitive_desc_create,
-        dnnl_rnn_direction_t, dnnl_status_t,
-    },
-    std::{ffi::c_uint, marker::PhantomData, sync::Arc},
+    super::rnn::{check_dims, RnnDescs},
+    crate::{error::DnnlError, memory::descriptor::MemoryDescriptor},
+    onednnl_sys::dnnl_dim_t,
 };
 
-pub struct ForwardAuGruConfig {
-    direction: dnnl_rnn_direction_t::Type,
-    src_layer_desc: MemoryDescriptor,
-    src_iter_desc: MemoryDescriptor,
-    attention_desc: MemoryDescriptor,
-    weights_layer_desc: MemoryDescriptor,
-    weights_iter_desc: MemoryDescriptor,
-    bias_desc: MemoryDescriptor,
-    dst_layer_desc: MemoryDescriptor,
-    dst_iter_desc: MemoryDescriptor,
-    flags: c_uint,
-    attr: PrimitiveAttributes,
-}
+/// Defines the forward and backward configs, their builders and the operations of an
+/// AUGRU cell. The plain and [linear-before-reset](super::lbr_augru) cells only differ
+/// in the oneDNN functions that create them, their rows of bias and their operation
+/// type, so both are generated from here.
+macro_rules! augru_primitive {
+    (
+        cell: $cell:literal,
+        bias_gates: $bias_gates:literal,
+        operation_type: $operation_type:ident,
+        $(#[$fwd_attr:meta])*
+        forward: $fwd_config:ident, $fwd_builder:ident, $fwd_op:ident, $fwd_create:ident,
+        $(#[$builder_attr:meta])*
+        builder,
+        $(#[$bwd_attr:meta])*
+        backward: $bwd_config:ident, $bwd_builder:ident, $bwd_op:ident, $bwd_create:ident,
+    ) => {
+        $(#[$fwd_attr])*
+        pub struct $fwd_config {
+            pub direction: $crate::primitives::rnn::RnnDirection,
+            pub src_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub src_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub attention_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub weights_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub weights_iter_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub bias_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub dst_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub dst_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub flags: ::std::ffi::c_uint,
+            pub attr: $crate::primitive::attributes::PrimitiveAttributes,
+        }
+
+        impl $fwd_config {
+            $(#[$builder_attr])*
+            pub fn builder(direction: $crate::primitives::rnn::RnnDirection) -> $fwd_builder {
+                $fwd_builder {
+                    direction,
+                    src_layer_desc: None,
+                    src_iter_desc: None,
+                    attention_desc: None,
+                    weights_layer_desc: None,
+                    weights_iter_desc: None,
+                    bias_desc: None,
+                    dst_layer_desc: None,
+                    dst_iter_desc: None,
+                    flags: $crate::onednnl_sys::dnnl_rnn_flags_t::dnnl_rnn_flags_undef,
+                    attr: None,
+                }
+            }
+
+            fn check_shapes(&self) -> Result<(), $crate::error::DnnlError> {
+                use $crate::primitives::{au_gru::check_augru_shapes, rnn::RnnDescs};
+
+                let descs = RnnDescs {
+                    direction: self.direction,
+                    src_layer: &self.src_layer_desc,
+                    src_iter: self.src_iter_desc.as_ref(),
+                    weights_layer: &self.weights_layer_desc,
+                    weights_iter: &self.weights_iter_desc,
+                    bias: self.bias_desc.as_ref(),
+                    dst_layer: &self.dst_layer_desc,
+                    dst_iter: self.dst_iter_desc.as_ref(),
+                };
 
-impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardAuGruConfig {
-    fn create_primitive_desc(
-        self,
-        engine: Arc<Engine>,
-    ) -> Result<PrimitiveDescriptor<'a, Forward, P, ForwardAuGruConfig>, DnnlError> {
-        let mut handle = std::ptr::null_mut();
-        let status = unsafe {
-            dnnl_augru_forward_primitive_desc_create(
-                &mut handle,
-                engine.handle,
-                P::KIND,
-                self.direction,
-                self.src_layer_desc.handle,
-                self.src_iter_desc.handle,
-                self.attention_desc.handle,
-                self.weights_layer_desc.handle,
-                self.weights_iter_desc.handle,
-                self.bias_desc.handle,
-                self.dst_layer_desc.handle,
-                self.dst_iter_desc.handle,
-                self.flags,
-                self.attr.handle,
-            )
-        };
-
-        if status == dnnl_status_t::dnnl_success {
-            Ok(PrimitiveDescriptor::<'a, Forward, P, ForwardAuGruConfig> {
-                handle,
-                config: self,
-
-                _marker_a: PhantomData,
-                _marker_d: PhantomData,
-                _marker_p: PhantomData,
-            })
-        } else {
-            Err(status.into())
+                check_augru_shapes(&descs, $bias_gates, &self.attention_desc)
+            }
         }
-    }
-}
 
-pub struct BackwardAuGruConfig<'a> {
-    direction: dnnl_rnn_direction_t::Type,
-    src_layer_desc: MemoryDescriptor,
-    src_iter_desc: MemoryDescriptor,
-    attention_desc: MemoryDescriptor,
-    weights_layer_desc: MemoryDescriptor,
-    weights_iter_desc: MemoryDescriptor,
-    bias_desc: MemoryDescriptor,
-    dst_layer_desc: MemoryDescriptor,
-    dst_iter_desc: MemoryDescriptor,
-    diff_src_layer_desc: MemoryDescriptor,
-    diff_src_iter_desc: MemoryDescriptor,
-    diff_attention_desc: MemoryDescriptor,
-    diff_weights_layer_desc: MemoryDescriptor,
-    diff_weights_iter_desc: MemoryDescriptor,
-    diff_bias_desc: MemoryDescriptor,
-    diff_dst_layer_desc: MemoryDescriptor,
-    diff_dst_iter_desc: MemoryDescriptor,
-    flags: c_uint,
-    hint_fwd_pd: &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardAuGruConfig>,
-    attr: PrimitiveAttributes,
-}
+        impl<'a, P: $crate::primitive::PropType<$crate::primitive::Forward>>
+            $crate::primitive::config::PrimitiveConfig<'a, $crate::primitive::Forward, P>
+            for $fwd_config
+        {
+            fn create_primitive_desc(
+                self,
+                engine: ::std::sync::Arc<$crate::engine::Engine>,
+            ) -> Result<
+                $crate::primitive::descriptor::PrimitiveDescriptor<
+                    'a,
+                    $crate::primitive::Forward,
+                    P,
+                    $fwd_config,
+                >,
+                $crate::error::DnnlError,
+            > {
+                use $crate::primitives::rnn::optional_handle;
+
+                self.check_shapes()?;
+
+                let mut handle = ::std::ptr::null_mut();
+                let status = unsafe {
+                    $crate::onednnl_sys::$fwd_create(
+                        &mut handle,
+                        engine.handle,
+                        P::KIND,
+                        self.direction.into(),
+                        self.src_layer_desc.handle,
+                        optional_handle(&self.src_iter_desc),
+                        self.attention_desc.handle,
+                        self.weights_layer_desc.handle,
+                        self.weights_iter_desc.handle,
+                        optional_handle(&self.bias_desc),
+                        self.dst_layer_desc.handle,
+                        optional_handle(&self.dst_iter_desc),
+                        self.flags,
+                        self.attr.handle,
+                    )
+                };
+
+                if status == $crate::onednnl_sys::dnnl_status_t::dnnl_success {
+                    Ok($crate::primitive::descriptor::PrimitiveDescriptor {
+                        handle,
+                        config: self,
 
-impl<'a, P: PropType<Backward>> PrimitiveConfig<'a, Backward, P> for BackwardAuGruConfig<'a> {
-    fn create_primitive_desc(
-        self,
-        engine: Arc<Engine>,
-    ) -> Result<PrimitiveDescriptor<'a, Backward, P, BackwardAuGruConfig<'a>>, DnnlError> {
-        let mut handle = std::ptr::null_mut();
-        let status = unsafe {
-            dnnl_augru_backward_primitive_desc_create(
-                &mut handle,
-                engine.handle,
-                P::KIND,
-                self.direction,
-                self.src_layer_desc.handle,
-                self.src_iter_desc.handle,
-                self.attention_desc.handle,
-                self.weights_layer_desc.handle,
-                self.weights_iter_desc.handle,
-                self.bias_desc.handle,
-                self.dst_layer_desc.handle,
-                self.dst_iter_desc.handle,
-                self.diff_src_layer_desc.handle,
-                self.diff_src_iter_desc.handle,
-                self.diff_attention_desc.handle,
-                self.diff_weights_layer_desc.handle,
-                self.diff_weights_iter_desc.handle,
-                self.diff_bias_desc.handle,
-                self.diff_dst_layer_desc.handle,
-                self.diff_dst_iter_desc.handle,
-                self.flags,
-                self.hint_fwd_pd.handle,
-                self.attr.handle,
-            )
-        };
-
-        if status == dnnl_status_t::dnnl_success {
-            Ok(
-                PrimitiveDescriptor::<'a, Backward, P, BackwardAuGruConfig<'a>> {
-                    handle,
-                    config: self,
-                    _marker_a: PhantomData,
-                    _marker_d: PhantomData,
-                    _marker_p: PhantomData,
-                },
-            )
-        } else {
-            Err(status.into())
+                        _marker_a: ::std::marker::PhantomData,
+                        _marker_d: ::std::marker::PhantomData,
+                        _marker_p: ::std::marker::PhantomData,
+                    })
+                } else {
+                    Err(status.into())
+                }
+            }
         }
-    }
-}
 
-pub struct ForwardAuGru<P: PropType<Forward>> {
-    pub prop_type: P,
-}
+        #[doc = concat!(
+            "Builder for [`",
+            stringify!($fwd_config),
+            "`] that checks the descriptor shapes against each\n",
+            "other before any primitive is created.",
+        )]
+        pub struct $fwd_builder {
+            direction: $crate::primitives::rnn::RnnDirection,
+            src_layer_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            src_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            attention_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            weights_layer_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            weights_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            bias_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            dst_layer_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            dst_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            flags: ::std::ffi::c_uint,
+            attr: Option<$crate::primitive::attributes::PrimitiveAttributes>,
+        }
+
+        impl $fwd_builder {
+            pub fn with_src_layer(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.src_layer_desc = Some(desc);
+                self
+            }
+
+            pub fn with_src_iter(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.src_iter_desc = Some(desc);
+                self
+            }
+
+            pub fn with_attention(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.attention_desc = Some(desc);
+                self
+            }
+
+            pub fn with_weights_layer(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.weights_layer_desc = Some(desc);
+                self
+            }
+
+            pub fn with_weights_iter(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.weights_iter_desc = Some(desc);
+                self
+            }
+
+            pub fn with_bias(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.bias_desc = Some(desc);
+                self
+            }
 
-impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardAuGru<P> {
-    const TYPE: OperationType = OperationType::Augru;
-    type OperationConfig = ForwardAuGruConfig;
+            pub fn with_dst_layer(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.dst_layer_desc = Some(desc);
+                self
+            }
+
+            pub fn with_dst_iter(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.dst_iter_desc = Some(desc);
+                self
+            }
+
+            pub fn with_flags(mut self, flags: ::std::ffi::c_uint) -> Self {
+                self.flags = flags;
+                self
+            }
+
+            pub fn with_attr(
+                mut self,
+                attr: $crate::primitive::attributes::PrimitiveAttributes,
+            ) -> Self {
+                self.attr = Some(attr);
+                self
+            }
+
+            /// Builds the configuration.
+            ///
+            /// Returns `DnnlError::InvalidArguments` if a required descriptor is missing and
+            /// `DnnlError::InvalidShape` if the shapes do not agree.
+            pub fn build(self) -> Result<$fwd_config, $crate::error::DnnlError> {
+                let config = $fwd_config {
+                    direction: self.direction,
+                    src_layer_desc: self
+                        .src_layer_desc
+                        .ok_or($crate::error::DnnlError::InvalidArguments)?,
+                    src_iter_desc: self.src_iter_desc,
+                    attention_desc: self
+                        .attention_desc
+                        .ok_or($crate::error::DnnlError::InvalidArguments)?,
+                    weights_layer_desc: self
+                        .weights_layer_desc
+                        .ok_or($crate::error::DnnlError::InvalidArguments)?,
+                    weights_iter_desc: self
+                        .weights_iter_desc
+                        .ok_or($crate::error::DnnlError::InvalidArguments)?,
+                    bias_desc: self.bias_desc,
+                    dst_layer_desc: self
+                        .dst_layer_desc
+                        .ok_or($crate::error::DnnlError::InvalidArguments)?,
+                    dst_iter_desc: self.dst_iter_desc,
+                    flags: self.flags,
+                    attr: match self.attr {
+                        Some(attr) => attr,
+                        None => $crate::primitive::attributes::PrimitiveAttributes::new()?,
+                    },
+                };
+
+                config.check_shapes()?;
+
+                Ok(config)
+            }
+        }
+
+        $(#[$bwd_attr])*
+        pub struct $bwd_config<'a> {
+            pub direction: $crate::primitives::rnn::RnnDirection,
+            pub src_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub src_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub attention_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub weights_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub weights_iter_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub bias_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub dst_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub dst_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub diff_src_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub diff_src_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub diff_attention_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub diff_weights_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub diff_weights_iter_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub diff_bias_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub diff_dst_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub diff_dst_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub flags: ::std::ffi::c_uint,
+            pub hint_fwd_pd: &'a $crate::primitive::descriptor::PrimitiveDescriptor<
+                'a,
+                $crate::primitive::Forward,
+                $crate::primitive::PropForwardTraining,
+                $fwd_config,
+            >,
+            pub attr: $crate::primitive::attributes::PrimitiveAttributes,
+        }
+
+        impl<'a> $bwd_config<'a> {
+            #[doc = concat!("Starts building a backward ", $cell, " configuration.")]
+            ///
+            /// The direction, flags and forward descriptors are taken from `hint_fwd_pd`, and
+            /// every diff descriptor defaults to a copy of its forward counterpart, so only the
+            /// descriptors that differ need to be set.
+            pub fn builder(
+                hint_fwd_pd: &'a $crate::primitive::descriptor::PrimitiveDescriptor<
+                    'a,
+                    $crate::primitive::Forward,
+                    $crate::primitive::PropForwardTraining,
+                    $fwd_config,
+                >,
+            ) -> $bwd_builder<'a> {
+                $bwd_builder {
+                    src_layer_desc: None,
+                    src_iter_desc: None,
+                    attention_desc: None,
+                    weights_layer_desc: None,
+                    weights_iter_desc: None,
+                    bias_desc: None,
+                    dst_layer_desc: None,
+                    dst_iter_desc: None,
+                    diff_src_layer_desc: None,
+                    diff_src_iter_desc: None,
+                    diff_attention_desc: None,
+                    diff_weights_layer_desc: None,
+                    diff_weights_iter_desc: None,
+                    diff_bias_desc: None,
+                    diff_dst_layer_desc: None,
+                    diff_dst_iter_desc: None,
+                    flags: hint_fwd_pd.config.flags,
+                    hint_fwd_pd,
+                    attr: None,
+                }
+            }
+
+            fn check_shapes(&self) -> Result<(), $crate::error::DnnlError> {
+                use $crate::primitives::{
+                    au_gru::check_augru_shapes,
+                    rnn::{check_same_dims, RnnDescs},
+                };
+
+                let descs = RnnDescs {
+                    direction: self.direction,
+                    src_layer: &self.src_layer_desc,
+                    src_iter: self.src_iter_desc.as_ref(),
+                    weights_layer: &self.weights_layer_desc,
+                    weights_iter: &self.weights_iter_desc,
+                    bias: self.bias_desc.as_ref(),
+                    dst_layer: &self.dst_layer_desc,
+                    dst_iter: self.dst_iter_desc.as_ref(),
+                };
+                let diff_descs = RnnDescs {
+                    direction: self.direction,
+                    src_layer: &self.diff_src_layer_desc,
+                    src_iter: self.diff_src_iter_desc.as_ref(),
+                    weights_layer: &self.diff_weights_layer_desc,
+                    weights_iter: &self.diff_weights_iter_desc,
+                    bias: self.diff_bias_desc.as_ref(),
+                    dst_layer: &self.diff_dst_layer_desc,
+                    dst_iter: self.diff_dst_iter_desc.as_ref(),
+                };
+
+                check_augru_shapes(&descs, $bias_gates, &self.attention_desc)?;
+                descs.check_diffs(&diff_descs)?;
+                check_same_dims(&self.attention_desc, &self.diff_attention_desc)
+            }
+        }
+
+        impl<'a, P: $crate::primitive::PropType<$crate::primitive::Backward>>
+            $crate::primitive::config::PrimitiveConfig<'a, $crate::primitive::Backward, P>
+            for $bwd_config<'a>
+        {
+            fn create_primitive_desc(
+                self,
+                engine: ::std::sync::Arc<$crate::engine::Engine>,
+            ) -> Result<
+                $crate::primitive::descriptor::PrimitiveDescriptor<
+                    'a,
+                    $crate::primitive::Backward,
+                    P,
+                    $bwd_config<'a>,
+                >,
+                $crate::error::DnnlError,
+            > {
+                use $crate::primitives::rnn::optional_handle;
+
+                self.check_shapes()?;
+
+                let mut handle = ::std::ptr::null_mut();
+                let status = unsafe {
+                    $crate::onednnl_sys::$bwd_create(
+                        &mut handle,
+                        engine.handle,
+                        P::KIND,
+                        self.direction.into(),
+                        self.src_layer_desc.handle,
+                        optional_handle(&self.src_iter_desc),
+                        self.attention_desc.handle,
+                        self.weights_layer_desc.handle,
+                        self.weights_iter_desc.handle,
+                        optional_handle(&self.bias_desc),
+                        self.dst_layer_desc.handle,
+                        optional_handle(&self.dst_iter_desc),
+                        self.diff_src_layer_desc.handle,
+                        optional_handle(&self.diff_src_iter_desc),
+                        self.diff_attention_desc.handle,
+                        self.diff_weights_layer_desc.handle,
+                        self.diff_weights_iter_desc.handle,
+                        optional_handle(&self.diff_bias_desc),
+                        self.diff_dst_layer_desc.handle,
+                        optional_handle(&self.diff_dst_iter_desc),
+                        self.flags,
+                        self.hint_fwd_pd.handle,
+                        self.attr.handle,
+                    )
+                };
+
+                if status == $crate::onednnl_sys::dnnl_status_t::dnnl_success {
+                    Ok($crate::primitive::descriptor::PrimitiveDescriptor {
+                        handle,
+                        config: self,
+                        _marker_a: ::std::marker::PhantomData,
+                        _marker_d: ::std::marker::PhantomData,
+                        _marker_p: ::std::marker::PhantomData,
+                    })
+                } else {
+                    Err(status.into())
+                }
+            }
+        }
+
+        #[doc = concat!(
+            "Builder for [`",
+            stringify!($bwd_config),
+            "`]. See [`",
+            stringify!($bwd_config),
+            "::builder`].",
+        )]
+        pub struct $bwd_builder<'a> {
+            src_layer_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            src_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            attention_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            weights_layer_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            weights_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            bias_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            dst_layer_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            dst_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            diff_src_layer_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            diff_src_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            diff_attention_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            diff_weights_layer_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            diff_weights_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            diff_bias_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            diff_dst_layer_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            diff_dst_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            flags: ::std::ffi::c_uint,
+            hint_fwd_pd: &'a $crate::primitive::descriptor::PrimitiveDescriptor<
+                'a,
+                $crate::primitive::Forward,
+                $crate::primitive::PropForwardTraining,
+                $fwd_config,
+            >,
+            attr: Option<$crate::primitive::attributes::PrimitiveAttributes>,
+        }
+
+        impl<'a> $bwd_builder<'a> {
+            pub fn with_src_layer(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.src_layer_desc = Some(desc);
+                self
+            }
+
+            pub fn with_src_iter(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.src_iter_desc = Some(desc);
+                self
+            }
+
+            pub fn with_attention(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.attention_desc = Some(desc);
+                self
+            }
+
+            pub fn with_weights_layer(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.weights_layer_desc = Some(desc);
+                self
+            }
+
+            pub fn with_weights_iter(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.weights_iter_desc = Some(desc);
+                self
+            }
+
+            pub fn with_bias(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.bias_desc = Some(desc);
+                self
+            }
+
+            pub fn with_dst_layer(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.dst_layer_desc = Some(desc);
+                self
+            }
+
+            pub fn with_dst_iter(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.dst_iter_desc = Some(desc);
+                self
+            }
+
+            pub fn with_diff_src_layer(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.diff_src_layer_desc = Some(desc);
+                self
+            }
+
+            pub fn with_diff_src_iter(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.diff_src_iter_desc = Some(desc);
+                self
+            }
+
+            pub fn with_diff_attention(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.diff_attention_desc = Some(desc);
+                self
+            }
+
+            pub fn with_diff_weights_layer(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.diff_weights_layer_desc = Some(desc);
+                self
+            }
+
+            pub fn with_diff_weights_iter(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.diff_weights_iter_desc = Some(desc);
+                self
+            }
+
+            pub fn with_diff_bias(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.diff_bias_desc = Some(desc);
+                self
+            }
+
+            pub fn with_diff_dst_layer(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.diff_dst_layer_desc = Some(desc);
+                self
+            }
+
+            pub fn with_diff_dst_iter(
+                mut self,
+                desc: $crate::memory::descriptor::MemoryDescriptor,
+            ) -> Self {
+                self.diff_dst_iter_desc = Some(desc);
+                self
+            }
+
+            pub fn with_flags(mut self, flags: ::std::ffi::c_uint) -> Self {
+                self.flags = flags;
+                self
+            }
+
+            pub fn with_attr(
+                mut self,
+                attr: $crate::primitive::attributes::PrimitiveAttributes,
+            ) -> Self {
+                self.attr = Some(attr);
+                self
+            }
+
+            /// Builds the configuration, filling in unset descriptors from the forward
+            /// primitive descriptor.
+            ///
+            /// Returns `DnnlError::InvalidShape` if the shapes do not agree.
+            pub fn build(self) -> Result<$bwd_config<'a>, $crate::error::DnnlError> {
+                let hint_fwd_pd = self.hint_fwd_pd;
+                let fwd = &hint_fwd_pd.config;
+
+                let or_clone =
+                    |desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+                     default: &$crate::memory::descriptor::MemoryDescriptor| {
+                        match desc {
+                            Some(desc) => Ok(desc),
+                            None => default.clone_desc(),
+                        }
+                    };
+                let or_clone_optional =
+                    |desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+                     default: Option<&$crate::memory::descriptor::MemoryDescriptor>| {
+                        match desc {
+                            Some(desc) => Ok(Some(desc)),
+                            None => default.map(|d| d.clone_desc()).transpose(),
+                        }
+                    };
+
+                let src_layer_desc = or_clone(self.src_layer_desc, &fwd.src_layer_desc)?;
+                let src_iter_desc =
+                    or_clone_optional(self.src_iter_desc, fwd.src_iter_desc.as_ref())?;
+                let attention_desc = or_clone(self.attention_desc, &fwd.attention_desc)?;
+                let weights_layer_desc =
+                    or_clone(self.weights_layer_desc, &fwd.weights_layer_desc)?;
+                let weights_iter_desc =
+                    or_clone(self.weights_iter_desc, &fwd.weights_iter_desc)?;
+                let bias_desc = or_clone_optional(self.bias_desc, fwd.bias_desc.as_ref())?;
+                let dst_layer_desc = or_clone(self.dst_layer_desc, &fwd.dst_layer_desc)?;
+                let dst_iter_desc =
+                    or_clone_optional(self.dst_iter_desc, fwd.dst_iter_desc.as_ref())?;
+
+                let config = $bwd_config {
+                    direction: fwd.direction,
+                    diff_src_layer_desc: or_clone(
+                        self.diff_src_layer_desc,
+                        &fwd.src_layer_desc,
+                    )?,
+                    diff_src_iter_desc: or_clone_optional(
+                        self.diff_src_iter_desc,
+                        fwd.src_iter_desc.as_ref(),
+                    )?,
+                    diff_attention_desc: or_clone(
+                        self.diff_attention_desc,
+                        &fwd.attention_desc,
+                    )?,
+                    diff_weights_layer_desc: or_clone(
+                        self.diff_weights_layer_desc,
+                        &fwd.weights_layer_desc,
+                    )?,
+                    diff_weights_iter_desc: or_clone(
+                        self.diff_weights_iter_desc,
+                        &fwd.weights_iter_desc,
+                    )?,
+                    diff_bias_desc: or_clone_optional(
+                        self.diff_bias_desc,
+                        fwd.bias_desc.as_ref(),
+                    )?,
+                    diff_dst_layer_desc: or_clone(
+                        self.diff_dst_layer_desc,
+                        &fwd.dst_layer_desc,
+                    )?,
+                    diff_dst_iter_desc: or_clone_optional(
+                        self.diff_dst_iter_desc,
+                        fwd.dst_iter_desc.as_ref(),
+                    )?,
+                    src_layer_desc,
+                    src_iter_desc,
+                    attention_desc,
+                    weights_layer_desc,
+                    weights_iter_desc,
+                    bias_desc,
+                    dst_layer_desc,
+                    dst_iter_desc,
+                    flags: self.flags,
+                    hint_fwd_pd,
+                    attr: match self.attr {
+                        Some(attr) => attr,
+                        None => $crate::primitive::attributes::PrimitiveAttributes::new()?,
+                    },
+                };
+
+                config.check_shapes()?;
+
+                Ok(config)
+            }
+        }
+
+        pub struct $fwd_op<P: $crate::primitive::PropType<$crate::primitive::Forward>> {
+            pub prop_type: P,
+        }
+
+        impl<P: $crate::primitive::PropType<$crate::primitive::Forward>>
+            $crate::primitive::Operation<'_, $crate::primitive::Forward, P> for $fwd_op<P>
+        {
+            const TYPE: $crate::primitive::OperationType =
+                $crate::primitive::OperationType::$operation_type;
+            type OperationConfig = $fwd_config;
+        }
+
+        pub struct $bwd_op<P: $crate::primitive::PropType<$crate::primitive::Backward>> {
+            pub prop_type: P,
+        }
+
+        impl<'a, P: $crate::primitive::PropType<$crate::primitive::Backward>>
+            $crate::primitive::Operation<'a, $crate::primitive::Backward, P> for $bwd_op<P>
+        {
+            const TYPE: $crate::primitive::OperationType =
+                $crate::primitive::OperationType::$operation_type;
+            type OperationConfig = $bwd_config<'a>;
+        }
+    };
 }
 
-pub struct BackwardAuGru<P: PropType<Backward>> {
-    pub prop_type: P,
+pub(super) use augru_primitive;
+
+augru_primitive! {
+    cell: "AUGRU",
+    bias_gates: 3,
+    operation_type: Augru,
+    /// Configuration for a forward AUGRU (GRU with attentional update gate).
+    ///
+    /// With `T` time steps, batch `N`, `L` layers, `D` directions, `SLC` input channels and
+    /// `DHC` hidden channels, the descriptors have the shapes
+    ///
+    /// * `src_layer_desc`: `[T, N, SLC]`
+    /// * `src_iter_desc`: `[L, D, N, DHC]`
+    /// * `attention_desc`: `[T, N, 1]`
+    /// * `weights_layer_desc`: `[L, D, SLC, 3, DHC]`
+    /// * `weights_iter_desc`: `[L, D, DHC, 3, DHC]`
+    /// * `bias_desc`: `[L, D, 3, DHC]`
+    /// * `dst_layer_desc`: `[T, N, DHC]`, or `[T, N, 2 * DHC]` for
+    ///   `RnnDirection::BidirectionalConcat`
+    /// * `dst_iter_desc`: `[L, D, N, DHC]`
+    ///
+    /// The gates are ordered update, reset, output. The optional descriptors may be
+    /// `None`, in which case the initial state is zero, there is no bias, or the final
+    /// state is not written. Use [`ForwardAuGruConfig::builder`] to have the shapes
+    /// checked against each other.
+    forward: ForwardAuGruConfig,
+        ForwardAuGruConfigBuilder,
+        ForwardAuGru,
+        dnnl_augru_forward_primitive_desc_create,
+    /// Starts building a forward AUGRU configuration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use onednnl::{
+    ///     memory::descriptor::{new_plain_descriptor, DataType},
+    ///     primitives::{au_gru::ForwardAuGruConfig, rnn::RnnDirection},
+    /// };
+    ///
+    /// let direction = RnnDirection::LeftToRight;
+    ///
+    /// // Two time steps, batch of one, four input and three hidden channels.
+    /// let config = ForwardAuGruConfig::builder(direction)
+    ///     .with_src_layer(new_plain_descriptor(3, vec![2, 1, 4], DataType::F32))
+    ///     .with_attention(new_plain_descriptor(3, vec![2, 1, 1], DataType::F32))
+    ///     .with_weights_layer(new_plain_descriptor(5, vec![1, 1, 4, 3, 3], DataType::F32))
+    ///     .with_weights_iter(new_plain_descriptor(5, vec![1, 1, 3, 3, 3], DataType::F32))
+    ///     .with_bias(new_plain_descriptor(4, vec![1, 1, 3, 3], DataType::F32))
+    ///     .with_dst_layer(new_plain_descriptor(3, vec![2, 1, 3], DataType::F32))
+    ///     .build();
+    ///
+    /// assert!(config.is_ok());
+    ///
+    /// // The attention has to cover every time step.
+    /// let config = ForwardAuGruConfig::builder(direction)
+    ///     .with_src_layer(new_plain_descriptor(3, vec![2, 1, 4], DataType::F32))
+    ///     .with_attention(new_plain_descriptor(3, vec![1, 1, 1], DataType::F32))
+    ///     .with_weights_layer(new_plain_descriptor(5, vec![1, 1, 4, 3, 3], DataType::F32))
+    ///     .with_weights_iter(new_plain_descriptor(5, vec![1, 1, 3, 3, 3], DataType::F32))
+    ///     .with_dst_layer(new_plain_descriptor(3, vec![2, 1, 3], DataType::F32))
+    ///     .build();
+    ///
+    /// assert!(config.is_err());
+    /// ```
+    builder,
+    /// Configuration for a backward AUGRU.
+    ///
+    /// Each `diff_*` descriptor has the shape of the forward descriptor it corresponds to.
+    /// CPU implementations expect `weights_layer_desc` and `weights_iter_desc` in the
+    /// `ldgoi` format and the diff weights in `ldigo`. Unless
+    /// `dnnl_rnn_flags_diff_weights_overwrite` is set, the diff weights and diff bias are
+    /// accumulated into, so they should start zeroed.
+    backward: BackwardAuGruConfig,
+        BackwardAuGruConfigBuilder,
+        BackwardAuGru,
+        dnnl_augru_backward_primitive_desc_create,
 }
 
-impl<'a, P: PropType<Backward>> Operation<'a, Backward, P> for BackwardAuGru<P> {
-    const TYPE: OperationType = OperationType::Augru;
-    type OperationConfig = BackwardAuGruConfig<'a>;
+/// Checks the shared RNN descriptors for a three gate cell with `bias_gates` rows of
+/// bias, and the attention against them, which has to be `[T, N, 1]`.
+pub(super) fn check_augru_shapes(
+    descs: &RnnDescs<'_>,
+    bias_gates: dnnl_dim_t,
+    attention_desc: &MemoryDescriptor,
+) -> Result<(), DnnlError> {
+    let (t, n) = descs.check(3, bias_gates)?;
+
+    check_dims(attention_desc, &[t, n, 1])
 }
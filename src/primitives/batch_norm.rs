@@ -1,21 +1,32 @@
 use {
+    super::NormalizationFlags,
     crate::{
         memory::descriptor::MemoryDescriptor,
         primitive::{
             attributes::PrimitiveAttributes, config::PrimitiveConfig,
-            descriptor::PrimitiveDescriptor, Forward, Operation, OperationType, PropType,
+            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
+            PropBackwardOrData, PropForwardTraining, PropType,
         },
     },
-    onednnl_sys::{dnnl_batch_normalization_forward_primitive_desc_create, dnnl_status_t},
-    std::{ffi::c_uint, marker::PhantomData},
+    onednnl_sys::{
+        dnnl_batch_normalization_backward_primitive_desc_create,
+        dnnl_batch_normalization_forward_primitive_desc_create, dnnl_status_t,
+    },
+    std::marker::PhantomData,
 };
 
+/// Configuration for a forward batch normalization.
+///
+/// The mean, variance, scale and shift hold one value per channel of `src_desc`.
+/// With `NormalizationFlags::FUSE_NORM_RELU` and `PropForwardTraining`, query the
+/// workspace with [`PrimitiveDescriptor::workspace_desc`] and pass it as
+/// `DNNL_ARG_WORKSPACE` to the forward and the backward primitive.
 pub struct ForwardBatchNormConfig {
-    src_desc: MemoryDescriptor,
-    dst_desc: MemoryDescriptor,
-    epsilon: f32,
-    flags: c_uint,
-    attr: PrimitiveAttributes,
+    pub src_desc: MemoryDescriptor,
+    pub dst_desc: MemoryDescriptor,
+    pub epsilon: f32,
+    pub flags: NormalizationFlags,
+    pub attr: PrimitiveAttributes,
 }
 
 impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardBatchNormConfig {
@@ -36,7 +47,7 @@ impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardBatchN
                 self.src_desc.handle,
                 self.dst_desc.handle,
                 self.epsilon,
-                self.flags,
+                self.flags.bits(),
                 self.attr.handle,
             )
         };
@@ -57,6 +68,68 @@ impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardBatchN
     }
 }
 
+/// Configuration for a backward batch normalization.
+///
+/// With `PropBackward` the primitive also computes `DNNL_ARG_DIFF_SCALE` and
+/// `DNNL_ARG_DIFF_SHIFT` for the flags that enable them, while `PropBackwardData`
+/// only computes `DNNL_ARG_DIFF_SRC`.
+pub struct BackwardBatchNormConfig<'a> {
+    pub diff_src_desc: MemoryDescriptor,
+    pub diff_dst_desc: MemoryDescriptor,
+    pub src_desc: MemoryDescriptor,
+    pub epsilon: f32,
+    pub flags: NormalizationFlags,
+    pub hint_fwd_pd:
+        &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardBatchNormConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a, P: PropBackwardOrData> PrimitiveConfig<'a, Backward, P> for BackwardBatchNormConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: std::sync::Arc<crate::engine::Engine>,
+    ) -> Result<
+        crate::primitive::descriptor::PrimitiveDescriptor<
+            'a,
+            Backward,
+            P,
+            BackwardBatchNormConfig<'a>,
+        >,
+        crate::error::DnnlError,
+    > {
+        let mut handle = std::ptr::null_mut();
+
+        let status = unsafe {
+            dnnl_batch_normalization_backward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.diff_src_desc.handle,
+                self.diff_dst_desc.handle,
+                self.src_desc.handle,
+                self.epsilon,
+                self.flags.bits(),
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(
+                PrimitiveDescriptor::<'a, Backward, P, BackwardBatchNormConfig<'a>> {
+                    handle,
+                    config: self,
+                    _marker_a: PhantomData,
+                    _marker_d: PhantomData,
+                    _marker_p: PhantomData,
+                },
+            )
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
 pub struct ForwardBatchNorm<P: PropType<Forward>> {
     pub prop_type: P,
 }
@@ -66,3 +139,13 @@ impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardBatchNorm<P> {
 
     type OperationConfig = ForwardBatchNormConfig;
 }
+
+pub struct BackwardBatchNorm<P: PropBackwardOrData> {
+    pub prop_type: P,
+}
+
+impl<'a, P: PropBackwardOrData> Operation<'a, Backward, P> for BackwardBatchNorm<P> {
+    const TYPE: OperationType = OperationType::BatchNormalization;
+
+    type OperationConfig = BackwardBatchNormConfig<'a>;
+}
@@ -1,20 +1,32 @@
 use {
     crate::{
-        memory::descriptor::MemoryDescriptor,
+        error::DnnlError,
+        memory::descriptor::{
+            new_plain_descriptor, DataTypeQuery, DimsQuery, MemoryDescriptor, NDimsQuery,
+        },
         primitive::{
             attributes::PrimitiveAttributes, config::PrimitiveConfig,
             descriptor::PrimitiveDescriptor, Forward, Operation, OperationType,
             PropForwardInference, PropType,
         },
     },
-    onednnl_sys::{dnnl_alg_kind_t, dnnl_binary_primitive_desc_create, dnnl_status_t},
+    onednnl_sys::{dnnl_alg_kind_t, dnnl_binary_primitive_desc_create_v2, dnnl_status_t},
     std::marker::PhantomData,
 };
 
+/// Configuration for a binary operation between `src0_desc` and `src1_desc`.
+///
+/// `src1_desc` can broadcast over `src0_desc` by having a size of 1 in some of its
+/// dimensions; [`broadcast_src1_desc`] builds the common cases.
+///
+/// [`Binary::SELECT`] takes a third, condition source in `src2_desc`, passed as
+/// `DNNL_ARG_SRC_2`, and picks `src0` where it is non-zero and `src1` elsewhere. The
+/// other algorithms leave `src2_desc` as `None`.
 pub struct ForwardBinaryConfig {
     pub alg_kind: dnnl_alg_kind_t::Type,
     pub src0_desc: MemoryDescriptor,
     pub src1_desc: MemoryDescriptor,
+    pub src2_desc: Option<MemoryDescriptor>,
     pub dst_desc: MemoryDescriptor,
     pub attr: PrimitiveAttributes,
 }
@@ -30,16 +42,23 @@ impl<'a> PrimitiveConfig<'a, Forward, PropForwardInference> for ForwardBinaryCon
             PropForwardInference,
             ForwardBinaryConfig,
         >,
-        crate::error::DnnlError,
+        DnnlError,
     > {
+        if self.alg_kind == Binary::SELECT && self.src2_desc.is_none() {
+            return Err(DnnlError::InvalidArguments);
+        }
+
         let mut handle = std::ptr::null_mut();
         let status = unsafe {
-            dnnl_binary_primitive_desc_create(
+            dnnl_binary_primitive_desc_create_v2(
                 &mut handle,
                 engine.handle,
                 self.alg_kind,
                 self.src0_desc.handle,
                 self.src1_desc.handle,
+                self.src2_desc
+                    .as_ref()
+                    .map_or(std::ptr::null(), |desc| desc.handle),
                 self.dst_desc.handle,
                 self.attr.handle,
             )
@@ -74,9 +93,76 @@ impl Binary {
     pub const MIN: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_binary_min;
     pub const MUL: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_binary_mul;
     pub const NE: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_binary_ne;
+    pub const SELECT: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_binary_select;
     pub const SUB: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_binary_sub;
 }
 
+/// How `src1` is broadcast over `src0` in a binary operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Broadcast {
+    /// A single value for the whole of `src0`.
+    PerTensor,
+    /// One value per channel, the second dimension of `src0`.
+    PerChannel,
+    /// One value per row, i.e. per index of every dimension of `src0` but the last.
+    PerRow,
+}
+
+/// Build a plain `src1_desc` with the data type of `src0_desc`, broadcast over it as
+/// described by `broadcast`.
+///
+/// `src0_desc` needs at least one dimension, and two for `PerChannel` and `PerRow`.
+/// Otherwise this returns `DnnlError::InvalidShape`.
+///
+/// ```
+/// use onednnl::{
+///     memory::descriptor::{new_plain_descriptor, DataType, DimsQuery},
+///     primitives::binary::{broadcast_src1_desc, Broadcast},
+/// };
+///
+/// let src0_desc = new_plain_descriptor(4, vec![2, 3, 4, 5], DataType::F32);
+///
+/// let per_channel = broadcast_src1_desc(&src0_desc, Broadcast::PerChannel).unwrap();
+/// assert_eq!(per_channel.query::<DimsQuery>(), Ok(vec![1, 3, 1, 1]));
+///
+/// let per_row = broadcast_src1_desc(&src0_desc, Broadcast::PerRow).unwrap();
+/// assert_eq!(per_row.query::<DimsQuery>(), Ok(vec![2, 3, 4, 1]));
+/// ```
+pub fn broadcast_src1_desc(
+    src0_desc: &MemoryDescriptor,
+    broadcast: Broadcast,
+) -> Result<MemoryDescriptor, DnnlError> {
+    let ndims = src0_desc.query::<NDimsQuery>()?;
+
+    let min_ndims = match broadcast {
+        Broadcast::PerTensor => 1,
+        Broadcast::PerChannel | Broadcast::PerRow => 2,
+    };
+
+    if ndims < min_ndims {
+        return Err(DnnlError::InvalidShape);
+    }
+
+    let src0_dims = src0_desc.query::<DimsQuery>()?;
+    let dims = src0_dims
+        .iter()
+        .enumerate()
+        .map(|(i, dim)| match broadcast {
+            Broadcast::PerTensor => 1,
+            Broadcast::PerChannel if i == 1 => *dim,
+            Broadcast::PerChannel => 1,
+            Broadcast::PerRow if i == src0_dims.len() - 1 => 1,
+            Broadcast::PerRow => *dim,
+        })
+        .collect();
+
+    Ok(new_plain_descriptor(
+        ndims,
+        dims,
+        src0_desc.query::<DataTypeQuery>()?,
+    ))
+}
+
 pub struct ForwardBinary<P: PropType<Forward>> {
     pub prop_type: P,
 }
@@ -0,0 +1,262 @@
+use {
+    super::check_spatial_dims,
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::MemoryDescriptor,
+        primitive::{
+            attributes::PrimitiveAttributes, config::PrimitiveConfig,
+            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
+            PropBackwardData, PropBackwardWeights, PropForwardTraining, PropType,
+        },
+    },
+    onednnl_sys::{
+        dnnl_alg_kind_t, dnnl_deconvolution_backward_data_primitive_desc_create,
+        dnnl_deconvolution_backward_weights_primitive_desc_create,
+        dnnl_deconvolution_forward_primitive_desc_create, dnnl_dim_t, dnnl_status_t,
+    },
+    std::{marker::PhantomData, sync::Arc},
+};
+
+/// Configuration for a forward deconvolution (transposed convolution).
+///
+/// `weights_desc` uses the same `[OC, IC, KH, KW]` layout as a convolution, with `OC`
+/// the number of output channels of the deconvolution. `strides`, `dilates`,
+/// `padding_l` and `padding_r` hold one entry per spatial dimension of `src_desc`, and
+/// a dilation of `0` means no dilation.
+pub struct ForwardDeconvolutionConfig {
+    pub alg_kind: dnnl_alg_kind_t::Type,
+    pub src_desc: MemoryDescriptor,
+    pub weights_desc: MemoryDescriptor,
+    pub bias_desc: Option<MemoryDescriptor>,
+    pub dst_desc: MemoryDescriptor,
+    pub strides: Vec<dnnl_dim_t>,
+    pub dilates: Vec<dnnl_dim_t>,
+    pub padding_l: Vec<dnnl_dim_t>,
+    pub padding_r: Vec<dnnl_dim_t>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardDeconvolutionConfig {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Forward, P, ForwardDeconvolutionConfig>, DnnlError> {
+        check_spatial_dims(
+            &self.src_desc,
+            &[
+                &self.strides,
+                &self.dilates,
+                &self.padding_l,
+                &self.padding_r,
+            ],
+        )?;
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_deconvolution_forward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.alg_kind,
+                self.src_desc.handle,
+                self.weights_desc.handle,
+                self.bias_desc
+                    .as_ref()
+                    .map_or(std::ptr::null_mut(), |d| d.handle),
+                self.dst_desc.handle,
+                self.strides.as_ptr(),
+                self.dilates.as_ptr(),
+                self.padding_l.as_ptr(),
+                self.padding_r.as_ptr(),
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(
+                PrimitiveDescriptor::<'a, Forward, P, ForwardDeconvolutionConfig> {
+                    handle,
+                    config: self,
+
+                    _marker_a: PhantomData,
+                    _marker_d: PhantomData,
+                    _marker_p: PhantomData,
+                },
+            )
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+pub struct BackwardDataDeconvolutionConfig<'a> {
+    pub alg_kind: dnnl_alg_kind_t::Type,
+    pub diff_src_desc: MemoryDescriptor,
+    pub weights_desc: MemoryDescriptor,
+    pub diff_dst_desc: MemoryDescriptor,
+    pub strides: Vec<dnnl_dim_t>,
+    pub dilates: Vec<dnnl_dim_t>,
+    pub padding_l: Vec<dnnl_dim_t>,
+    pub padding_r: Vec<dnnl_dim_t>,
+    pub hint_fwd_pd:
+        &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardDeconvolutionConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a> PrimitiveConfig<'a, Backward, PropBackwardData> for BackwardDataDeconvolutionConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<
+        PrimitiveDescriptor<'a, Backward, PropBackwardData, BackwardDataDeconvolutionConfig<'a>>,
+        DnnlError,
+    > {
+        check_spatial_dims(
+            &self.diff_src_desc,
+            &[
+                &self.strides,
+                &self.dilates,
+                &self.padding_l,
+                &self.padding_r,
+            ],
+        )?;
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_deconvolution_backward_data_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                self.alg_kind,
+                self.diff_src_desc.handle,
+                self.weights_desc.handle,
+                self.diff_dst_desc.handle,
+                self.strides.as_ptr(),
+                self.dilates.as_ptr(),
+                self.padding_l.as_ptr(),
+                self.padding_r.as_ptr(),
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+pub struct BackwardWeightsDeconvolutionConfig<'a> {
+    pub alg_kind: dnnl_alg_kind_t::Type,
+    pub src_desc: MemoryDescriptor,
+    pub diff_weights_desc: MemoryDescriptor,
+    pub diff_bias_desc: Option<MemoryDescriptor>,
+    pub diff_dst_desc: MemoryDescriptor,
+    pub strides: Vec<dnnl_dim_t>,
+    pub dilates: Vec<dnnl_dim_t>,
+    pub padding_l: Vec<dnnl_dim_t>,
+    pub padding_r: Vec<dnnl_dim_t>,
+    pub hint_fwd_pd:
+        &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardDeconvolutionConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a> PrimitiveConfig<'a, Backward, PropBackwardWeights>
+    for BackwardWeightsDeconvolutionConfig<'a>
+{
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<
+        PrimitiveDescriptor<
+            'a,
+            Backward,
+            PropBackwardWeights,
+            BackwardWeightsDeconvolutionConfig<'a>,
+        >,
+        DnnlError,
+    > {
+        check_spatial_dims(
+            &self.src_desc,
+            &[
+                &self.strides,
+                &self.dilates,
+                &self.padding_l,
+                &self.padding_r,
+            ],
+        )?;
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_deconvolution_backward_weights_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                self.alg_kind,
+                self.src_desc.handle,
+                self.diff_weights_desc.handle,
+                self.diff_bias_desc
+                    .as_ref()
+                    .map_or(std::ptr::null_mut(), |d| d.handle),
+                self.diff_dst_desc.handle,
+                self.strides.as_ptr(),
+                self.dilates.as_ptr(),
+                self.padding_l.as_ptr(),
+                self.padding_r.as_ptr(),
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+pub struct Deconvolution;
+
+impl Deconvolution {
+    pub const DIRECT: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_deconvolution_direct;
+    pub const WINOGRAD: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_deconvolution_winograd;
+}
+
+pub struct ForwardDeconvolution<P: PropType<Forward>> {
+    pub prop_type: P,
+}
+
+impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardDeconvolution<P> {
+    const TYPE: OperationType = OperationType::Deconvolution;
+    type OperationConfig = ForwardDeconvolutionConfig;
+}
+
+pub struct BackwardDataDeconvolution;
+
+impl<'a> Operation<'a, Backward, PropBackwardData> for BackwardDataDeconvolution {
+    const TYPE: OperationType = OperationType::Deconvolution;
+    type OperationConfig = BackwardDataDeconvolutionConfig<'a>;
+}
+
+pub struct BackwardWeightsDeconvolution;
+
+impl<'a> Operation<'a, Backward, PropBackwardWeights> for BackwardWeightsDeconvolution {
+    const TYPE: OperationType = OperationType::Deconvolution;
+    type OperationConfig = BackwardWeightsDeconvolutionConfig<'a>;
+}
@@ -0,0 +1,142 @@
+use {
+    super::NormalizationFlags,
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::MemoryDescriptor,
+        primitive::{
+            attributes::PrimitiveAttributes, config::PrimitiveConfig,
+            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
+            PropBackwardOrData, PropForwardTraining, PropType,
+        },
+    },
+    onednnl_sys::{
+        dnnl_dim_t, dnnl_group_normalization_backward_primitive_desc_create,
+        dnnl_group_normalization_forward_primitive_desc_create, dnnl_status_t,
+    },
+    std::{marker::PhantomData, sync::Arc},
+};
+
+/// Configuration for a forward group normalization.
+///
+/// The channels of `src_desc` are split into `groups` groups of equal size, and each
+/// group is normalized over its channels and the spatial dimensions. The mean and
+/// variance have shape `[N, groups]`, while scale and shift, enabled through `flags`,
+/// hold one value per channel.
+pub struct ForwardGroupNormConfig {
+    pub src_desc: MemoryDescriptor,
+    pub dst_desc: MemoryDescriptor,
+    pub groups: dnnl_dim_t,
+    pub epsilon: f32,
+    pub flags: NormalizationFlags,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardGroupNormConfig {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Forward, P, ForwardGroupNormConfig>, DnnlError> {
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_group_normalization_forward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.src_desc.handle,
+                self.dst_desc.handle,
+                self.groups,
+                self.epsilon,
+                self.flags.bits(),
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(
+                PrimitiveDescriptor::<'a, Forward, P, ForwardGroupNormConfig> {
+                    handle,
+                    config: self,
+
+                    _marker_a: PhantomData,
+                    _marker_d: PhantomData,
+                    _marker_p: PhantomData,
+                },
+            )
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+/// Configuration for a backward group normalization.
+///
+/// With `PropBackward` the primitive also computes `DNNL_ARG_DIFF_SCALE` and
+/// `DNNL_ARG_DIFF_SHIFT` for the flags that enable them, while `PropBackwardData`
+/// only computes `DNNL_ARG_DIFF_SRC`.
+pub struct BackwardGroupNormConfig<'a> {
+    pub diff_src_desc: MemoryDescriptor,
+    pub diff_dst_desc: MemoryDescriptor,
+    pub src_desc: MemoryDescriptor,
+    pub groups: dnnl_dim_t,
+    pub epsilon: f32,
+    pub flags: NormalizationFlags,
+    pub hint_fwd_pd:
+        &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardGroupNormConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a, P: PropBackwardOrData> PrimitiveConfig<'a, Backward, P> for BackwardGroupNormConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Backward, P, BackwardGroupNormConfig<'a>>, DnnlError> {
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_group_normalization_backward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.diff_src_desc.handle,
+                self.diff_dst_desc.handle,
+                self.src_desc.handle,
+                self.groups,
+                self.epsilon,
+                self.flags.bits(),
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+pub struct ForwardGroupNorm<P: PropType<Forward>> {
+    pub prop_type: P,
+}
+
+impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardGroupNorm<P> {
+    const TYPE: OperationType = OperationType::GroupNormalization;
+    type OperationConfig = ForwardGroupNormConfig;
+}
+
+pub struct BackwardGroupNorm<P: PropBackwardOrData> {
+    pub prop_type: P,
+}
+
+impl<'a, P: PropBackwardOrData> Operation<'a, Backward, P> for BackwardGroupNorm<P> {
+    const TYPE: OperationType = OperationType::GroupNormalization;
+    type OperationConfig = BackwardGroupNormConfig<'a>;
+}
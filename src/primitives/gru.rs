@@ -0,0 +1,281 @@
+/// Defines the forward and backward configs and the operations of a GRU cell. The
+/// plain and [linear-before-reset](super::lbr_gru) cells only differ in the oneDNN
+/// functions that create them, their rows of bias and their operation type, so both
+/// are generated from here.
+macro_rules! gru_primitive {
+    (
+        bias_gates: $bias_gates:literal,
+        operation_type: $operation_type:ident,
+        $(#[$fwd_attr:meta])*
+        forward: $fwd_config:ident, $fwd_op:ident, $fwd_create:ident,
+        $(#[$bwd_attr:meta])*
+        backward: $bwd_config:ident, $bwd_op:ident, $bwd_create:ident,
+    ) => {
+        $(#[$fwd_attr])*
+        pub struct $fwd_config {
+            pub direction: $crate::primitives::rnn::RnnDirection,
+            pub src_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub src_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub weights_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub weights_iter_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub bias_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub dst_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub dst_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub flags: ::std::ffi::c_uint,
+            pub attr: $crate::primitive::attributes::PrimitiveAttributes,
+        }
+
+        impl $fwd_config {
+            fn check_shapes(&self) -> Result<(), $crate::error::DnnlError> {
+                use $crate::primitives::rnn::RnnDescs;
+
+                let descs = RnnDescs {
+                    direction: self.direction,
+                    src_layer: &self.src_layer_desc,
+                    src_iter: self.src_iter_desc.as_ref(),
+                    weights_layer: &self.weights_layer_desc,
+                    weights_iter: &self.weights_iter_desc,
+                    bias: self.bias_desc.as_ref(),
+                    dst_layer: &self.dst_layer_desc,
+                    dst_iter: self.dst_iter_desc.as_ref(),
+                };
+
+                descs.check(3, $bias_gates)?;
+
+                Ok(())
+            }
+        }
+
+        impl<'a, P: $crate::primitive::PropType<$crate::primitive::Forward>>
+            $crate::primitive::config::PrimitiveConfig<'a, $crate::primitive::Forward, P>
+            for $fwd_config
+        {
+            fn create_primitive_desc(
+                self,
+                engine: ::std::sync::Arc<$crate::engine::Engine>,
+            ) -> Result<
+                $crate::primitive::descriptor::PrimitiveDescriptor<
+                    'a,
+                    $crate::primitive::Forward,
+                    P,
+                    $fwd_config,
+                >,
+                $crate::error::DnnlError,
+            > {
+                use $crate::primitives::rnn::optional_handle;
+
+                self.check_shapes()?;
+
+                let mut handle = ::std::ptr::null_mut();
+                let status = unsafe {
+                    $crate::onednnl_sys::$fwd_create(
+                        &mut handle,
+                        engine.handle,
+                        P::KIND,
+                        self.direction.into(),
+                        self.src_layer_desc.handle,
+                        optional_handle(&self.src_iter_desc),
+                        self.weights_layer_desc.handle,
+                        self.weights_iter_desc.handle,
+                        optional_handle(&self.bias_desc),
+                        self.dst_layer_desc.handle,
+                        optional_handle(&self.dst_iter_desc),
+                        self.flags,
+                        self.attr.handle,
+                    )
+                };
+
+                if status == $crate::onednnl_sys::dnnl_status_t::dnnl_success {
+                    Ok($crate::primitive::descriptor::PrimitiveDescriptor {
+                        handle,
+                        config: self,
+
+                        _marker_a: ::std::marker::PhantomData,
+                        _marker_d: ::std::marker::PhantomData,
+                        _marker_p: ::std::marker::PhantomData,
+                    })
+                } else {
+                    Err(status.into())
+                }
+            }
+        }
+
+        $(#[$bwd_attr])*
+        pub struct $bwd_config<'a> {
+            pub direction: $crate::primitives::rnn::RnnDirection,
+            pub src_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub src_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub weights_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub weights_iter_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub bias_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub dst_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub dst_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub diff_src_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub diff_src_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub diff_weights_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub diff_weights_iter_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub diff_bias_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub diff_dst_layer_desc: $crate::memory::descriptor::MemoryDescriptor,
+            pub diff_dst_iter_desc: Option<$crate::memory::descriptor::MemoryDescriptor>,
+            pub flags: ::std::ffi::c_uint,
+            pub hint_fwd_pd: &'a $crate::primitive::descriptor::PrimitiveDescriptor<
+                'a,
+                $crate::primitive::Forward,
+                $crate::primitive::PropForwardTraining,
+                $fwd_config,
+            >,
+            pub attr: $crate::primitive::attributes::PrimitiveAttributes,
+        }
+
+        impl $bwd_config<'_> {
+            fn check_shapes(&self) -> Result<(), $crate::error::DnnlError> {
+                use $crate::primitives::rnn::RnnDescs;
+
+                let descs = RnnDescs {
+                    direction: self.direction,
+                    src_layer: &self.src_layer_desc,
+                    src_iter: self.src_iter_desc.as_ref(),
+                    weights_layer: &self.weights_layer_desc,
+                    weights_iter: &self.weights_iter_desc,
+                    bias: self.bias_desc.as_ref(),
+                    dst_layer: &self.dst_layer_desc,
+                    dst_iter: self.dst_iter_desc.as_ref(),
+                };
+                let diff_descs = RnnDescs {
+                    direction: self.direction,
+                    src_layer: &self.diff_src_layer_desc,
+                    src_iter: self.diff_src_iter_desc.as_ref(),
+                    weights_layer: &self.diff_weights_layer_desc,
+                    weights_iter: &self.diff_weights_iter_desc,
+                    bias: self.diff_bias_desc.as_ref(),
+                    dst_layer: &self.diff_dst_layer_desc,
+                    dst_iter: self.diff_dst_iter_desc.as_ref(),
+                };
+
+                descs.check(3, $bias_gates)?;
+                descs.check_diffs(&diff_descs)
+            }
+        }
+
+        impl<'a, P: $crate::primitive::PropType<$crate::primitive::Backward>>
+            $crate::primitive::config::PrimitiveConfig<'a, $crate::primitive::Backward, P>
+            for $bwd_config<'a>
+        {
+            fn create_primitive_desc(
+                self,
+                engine: ::std::sync::Arc<$crate::engine::Engine>,
+            ) -> Result<
+                $crate::primitive::descriptor::PrimitiveDescriptor<
+                    'a,
+                    $crate::primitive::Backward,
+                    P,
+                    $bwd_config<'a>,
+                >,
+                $crate::error::DnnlError,
+            > {
+                use $crate::primitives::rnn::optional_handle;
+
+                self.check_shapes()?;
+
+                let mut handle = ::std::ptr::null_mut();
+                let status = unsafe {
+                    $crate::onednnl_sys::$bwd_create(
+                        &mut handle,
+                        engine.handle,
+                        P::KIND,
+                        self.direction.into(),
+                        self.src_layer_desc.handle,
+                        optional_handle(&self.src_iter_desc),
+                        self.weights_layer_desc.handle,
+                        self.weights_iter_desc.handle,
+                        optional_handle(&self.bias_desc),
+                        self.dst_layer_desc.handle,
+                        optional_handle(&self.dst_iter_desc),
+                        self.diff_src_layer_desc.handle,
+                        optional_handle(&self.diff_src_iter_desc),
+                        self.diff_weights_layer_desc.handle,
+                        self.diff_weights_iter_desc.handle,
+                        optional_handle(&self.diff_bias_desc),
+                        self.diff_dst_layer_desc.handle,
+                        optional_handle(&self.diff_dst_iter_desc),
+                        self.flags,
+                        self.hint_fwd_pd.handle,
+                        self.attr.handle,
+                    )
+                };
+
+                if status == $crate::onednnl_sys::dnnl_status_t::dnnl_success {
+                    Ok($crate::primitive::descriptor::PrimitiveDescriptor {
+                        handle,
+                        config: self,
+
+                        _marker_a: ::std::marker::PhantomData,
+                        _marker_d: ::std::marker::PhantomData,
+                        _marker_p: ::std::marker::PhantomData,
+                    })
+                } else {
+                    Err(status.into())
+                }
+            }
+        }
+
+        pub struct $fwd_op<P: $crate::primitive::PropType<$crate::primitive::Forward>> {
+            pub prop_type: P,
+        }
+
+        impl<P: $crate::primitive::PropType<$crate::primitive::Forward>>
+            $crate::primitive::Operation<'_, $crate::primitive::Forward, P> for $fwd_op<P>
+        {
+            const TYPE: $crate::primitive::OperationType =
+                $crate::primitive::OperationType::$operation_type;
+            type OperationConfig = $fwd_config;
+        }
+
+        pub struct $bwd_op<P: $crate::primitive::PropType<$crate::primitive::Backward>> {
+            pub prop_type: P,
+        }
+
+        impl<'a, P: $crate::primitive::PropType<$crate::primitive::Backward>>
+            $crate::primitive::Operation<'a, $crate::primitive::Backward, P> for $bwd_op<P>
+        {
+            const TYPE: $crate::primitive::OperationType =
+                $crate::primitive::OperationType::$operation_type;
+            type OperationConfig = $bwd_config<'a>;
+        }
+    };
+}
+
+pub(super) use gru_primitive;
+
+gru_primitive! {
+    bias_gates: 3,
+    operation_type: Gru,
+    /// Configuration for a forward GRU.
+    ///
+    /// With `T` time steps, batch `N`, `L` layers, `D` directions, `SLC` input channels and
+    /// `DHC` hidden channels, the descriptors have the shapes
+    ///
+    /// * `src_layer_desc`: `[T, N, SLC]`
+    /// * `src_iter_desc`, `dst_iter_desc`: `[L, D, N, DHC]`
+    /// * `weights_layer_desc`: `[L, D, SLC, 3, DHC]`
+    /// * `weights_iter_desc`: `[L, D, DHC, 3, DHC]`
+    /// * `bias_desc`: `[L, D, 3, DHC]`
+    /// * `dst_layer_desc`: `[T, N, DHC]`, or `[T, N, 2 * DHC]` for
+    ///   `RnnDirection::BidirectionalConcat`
+    ///
+    /// The gates are ordered update, reset, output, and the reset gate is applied to the
+    /// hidden state before it is multiplied by the output gate weights. The optional
+    /// descriptors may be `None`, in which case the initial state is zero, there is no bias,
+    /// or the final state is not written. The shapes are checked against each other when
+    /// the primitive descriptor is created.
+    forward: ForwardGruConfig, ForwardGru, dnnl_gru_forward_primitive_desc_create,
+    /// Configuration for a backward GRU.
+    ///
+    /// Each `diff_*` descriptor has the shape of the forward descriptor it corresponds to,
+    /// and an optional one may only be set when its forward descriptor is. CPU
+    /// implementations expect `weights_layer_desc` and `weights_iter_desc` in the `ldgoi`
+    /// format and the diff weights in `ldigo`. Unless `dnnl_rnn_flags_diff_weights_overwrite`
+    /// is set, the diff weights and diff bias are accumulated into, so they should start
+    /// zeroed.
+    backward: BackwardGruConfig, BackwardGru, dnnl_gru_backward_primitive_desc_create,
+}
@@ -0,0 +1,147 @@
+use {
+    super::NormalizationFlags,
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::MemoryDescriptor,
+        primitive::{
+            attributes::PrimitiveAttributes, config::PrimitiveConfig,
+            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
+            PropBackwardOrData, PropForwardTraining, PropType,
+        },
+    },
+    onednnl_sys::{
+        dnnl_layer_normalization_backward_primitive_desc_create,
+        dnnl_layer_normalization_forward_primitive_desc_create, dnnl_status_t,
+    },
+    std::{marker::PhantomData, sync::Arc},
+};
+
+/// Configuration for a forward layer normalization over the last dimension of
+/// `src_desc`.
+///
+/// `stat_desc` describes the mean and variance, which have the shape of `src_desc`
+/// without its last dimension. When it is `None`, oneDNN derives it from `src_desc`.
+/// Scale and shift, enabled through `flags`, hold one value per element of the last
+/// dimension.
+pub struct ForwardLayerNormConfig {
+    pub src_desc: MemoryDescriptor,
+    pub dst_desc: MemoryDescriptor,
+    pub stat_desc: Option<MemoryDescriptor>,
+    pub epsilon: f32,
+    pub flags: NormalizationFlags,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardLayerNormConfig {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Forward, P, ForwardLayerNormConfig>, DnnlError> {
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_layer_normalization_forward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.src_desc.handle,
+                self.dst_desc.handle,
+                self.stat_desc
+                    .as_ref()
+                    .map_or(std::ptr::null_mut(), |d| d.handle),
+                self.epsilon,
+                self.flags.bits(),
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(
+                PrimitiveDescriptor::<'a, Forward, P, ForwardLayerNormConfig> {
+                    handle,
+                    config: self,
+
+                    _marker_a: PhantomData,
+                    _marker_d: PhantomData,
+                    _marker_p: PhantomData,
+                },
+            )
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+/// Configuration for a backward layer normalization.
+///
+/// With `PropBackward` the primitive also computes `DNNL_ARG_DIFF_SCALE` and
+/// `DNNL_ARG_DIFF_SHIFT` for the flags that enable them, while `PropBackwardData`
+/// only computes `DNNL_ARG_DIFF_SRC`.
+pub struct BackwardLayerNormConfig<'a> {
+    pub diff_src_desc: MemoryDescriptor,
+    pub diff_dst_desc: MemoryDescriptor,
+    pub src_desc: MemoryDescriptor,
+    pub stat_desc: Option<MemoryDescriptor>,
+    pub epsilon: f32,
+    pub flags: NormalizationFlags,
+    pub hint_fwd_pd:
+        &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardLayerNormConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a, P: PropBackwardOrData> PrimitiveConfig<'a, Backward, P> for BackwardLayerNormConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Backward, P, BackwardLayerNormConfig<'a>>, DnnlError> {
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_layer_normalization_backward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.diff_src_desc.handle,
+                self.diff_dst_desc.handle,
+                self.src_desc.handle,
+                self.stat_desc
+                    .as_ref()
+                    .map_or(std::ptr::null_mut(), |d| d.handle),
+                self.epsilon,
+                self.flags.bits(),
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+pub struct ForwardLayerNorm<P: PropType<Forward>> {
+    pub prop_type: P,
+}
+
+impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardLayerNorm<P> {
+    const TYPE: OperationType = OperationType::LayerNormalization;
+    type OperationConfig = ForwardLayerNormConfig;
+}
+
+pub struct BackwardLayerNorm<P: PropBackwardOrData> {
+    pub prop_type: P,
+}
+
+impl<'a, P: PropBackwardOrData> Operation<'a, Backward, P> for BackwardLayerNorm<P> {
+    const TYPE: OperationType = OperationType::LayerNormalization;
+    type OperationConfig = BackwardLayerNormConfig<'a>;
+}
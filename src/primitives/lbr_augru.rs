@@ -0,0 +1,80 @@
+use super::au_gru::augru_primitive;
+
+augru_primitive! {
+    cell: "linear-before-reset AUGRU",
+    bias_gates: 4,
+    operation_type: LbrAuGru,
+    /// Configuration for a forward linear-before-reset AUGRU.
+    ///
+    /// With `T` time steps, batch `N`, `L` layers, `D` directions, `SLC` input channels and
+    /// `DHC` hidden channels, the descriptors have the shapes
+    ///
+    /// * `src_layer_desc`: `[T, N, SLC]`
+    /// * `src_iter_desc`: `[L, D, N, DHC]`
+    /// * `attention_desc`: `[T, N, 1]`
+    /// * `weights_layer_desc`: `[L, D, SLC, 3, DHC]`
+    /// * `weights_iter_desc`: `[L, D, DHC, 3, DHC]`
+    /// * `bias_desc`: `[L, D, 4, DHC]`
+    /// * `dst_layer_desc`: `[T, N, DHC]`, or `[T, N, 2 * DHC]` for
+    ///   `RnnDirection::BidirectionalConcat`
+    /// * `dst_iter_desc`: `[L, D, N, DHC]`
+    ///
+    /// The gates are ordered update, reset, output. As in [`super::lbr_gru`], the reset
+    /// gate is applied after the hidden state is multiplied by the output gate weights,
+    /// and the fourth bias row is added to that product. The optional descriptors may be
+    /// `None`, in which case the initial state is zero, there is no bias, or the final
+    /// state is not written. Use [`ForwardLbrAuGruConfig::builder`] to have the shapes
+    /// checked against each other.
+    forward: ForwardLbrAuGruConfig,
+        ForwardLbrAuGruConfigBuilder,
+        ForwardLbrAuGru,
+        dnnl_lbr_augru_forward_primitive_desc_create,
+    /// Starts building a forward linear-before-reset AUGRU configuration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use onednnl::{
+    ///     memory::descriptor::{new_plain_descriptor, DataType},
+    ///     primitives::{lbr_augru::ForwardLbrAuGruConfig, rnn::RnnDirection},
+    /// };
+    ///
+    /// let direction = RnnDirection::LeftToRight;
+    ///
+    /// // Two time steps, batch of one, four input and three hidden channels.
+    /// let config = ForwardLbrAuGruConfig::builder(direction)
+    ///     .with_src_layer(new_plain_descriptor(3, vec![2, 1, 4], DataType::F32))
+    ///     .with_attention(new_plain_descriptor(3, vec![2, 1, 1], DataType::F32))
+    ///     .with_weights_layer(new_plain_descriptor(5, vec![1, 1, 4, 3, 3], DataType::F32))
+    ///     .with_weights_iter(new_plain_descriptor(5, vec![1, 1, 3, 3, 3], DataType::F32))
+    ///     .with_bias(new_plain_descriptor(4, vec![1, 1, 4, 3], DataType::F32))
+    ///     .with_dst_layer(new_plain_descriptor(3, vec![2, 1, 3], DataType::F32))
+    ///     .build();
+    ///
+    /// assert!(config.is_ok());
+    ///
+    /// // The bias needs the extra row for the output gate.
+    /// let config = ForwardLbrAuGruConfig::builder(direction)
+    ///     .with_src_layer(new_plain_descriptor(3, vec![2, 1, 4], DataType::F32))
+    ///     .with_attention(new_plain_descriptor(3, vec![2, 1, 1], DataType::F32))
+    ///     .with_weights_layer(new_plain_descriptor(5, vec![1, 1, 4, 3, 3], DataType::F32))
+    ///     .with_weights_iter(new_plain_descriptor(5, vec![1, 1, 3, 3, 3], DataType::F32))
+    ///     .with_bias(new_plain_descriptor(4, vec![1, 1, 3, 3], DataType::F32))
+    ///     .with_dst_layer(new_plain_descriptor(3, vec![2, 1, 3], DataType::F32))
+    ///     .build();
+    ///
+    /// assert!(config.is_err());
+    /// ```
+    builder,
+    /// Configuration for a backward linear-before-reset AUGRU.
+    ///
+    /// Each `diff_*` descriptor has the shape of the forward descriptor it corresponds to.
+    /// CPU implementations expect `weights_layer_desc` and `weights_iter_desc` in the
+    /// `ldgoi` format and the diff weights in `ldigo`. Unless
+    /// `dnnl_rnn_flags_diff_weights_overwrite` is set, the diff weights and diff bias are
+    /// accumulated into, so they should start zeroed.
+    backward: BackwardLbrAuGruConfig,
+        BackwardLbrAuGruConfigBuilder,
+        BackwardLbrAuGru,
+        dnnl_lbr_augru_backward_primitive_desc_create,
+}
@@ -0,0 +1,38 @@
+use super::gru::gru_primitive;
+
+gru_primitive! {
+    bias_gates: 4,
+    operation_type: LbrGru,
+    /// Configuration for a forward linear-before-reset GRU, the variant PyTorch's `GRU`
+    /// implements.
+    ///
+    /// With `T` time steps, batch `N`, `L` layers, `D` directions, `SLC` input channels and
+    /// `DHC` hidden channels, the descriptors have the shapes
+    ///
+    /// * `src_layer_desc`: `[T, N, SLC]`
+    /// * `src_iter_desc`, `dst_iter_desc`: `[L, D, N, DHC]`
+    /// * `weights_layer_desc`: `[L, D, SLC, 3, DHC]`
+    /// * `weights_iter_desc`: `[L, D, DHC, 3, DHC]`
+    /// * `bias_desc`: `[L, D, 4, DHC]`
+    /// * `dst_layer_desc`: `[T, N, DHC]`, or `[T, N, 2 * DHC]` for
+    ///   `RnnDirection::BidirectionalConcat`
+    ///
+    /// The gates are ordered update, reset, output. Unlike [`super::gru`], the reset gate
+    /// is applied after the hidden state is multiplied by the output gate weights, and
+    /// the fourth bias row is added to that product before the reset. The optional
+    /// descriptors may be `None`, in which case the initial state is zero, there is no bias,
+    /// or the final state is not written. The shapes are checked against each other when
+    /// the primitive descriptor is created.
+    forward: ForwardLbrGruConfig, ForwardLbrGru, dnnl_lbr_gru_forward_primitive_desc_create,
+    /// Configuration for a backward linear-before-reset GRU.
+    ///
+    /// Each `diff_*` descriptor has the shape of the forward descriptor it corresponds to,
+    /// and an optional one may only be set when its forward descriptor is. CPU
+    /// implementations expect `weights_layer_desc` and `weights_iter_desc` in the `ldgoi`
+    /// format and the diff weights in `ldigo`. Unless `dnnl_rnn_flags_diff_weights_overwrite`
+    /// is set, the diff weights and diff bias are accumulated into, so they should start
+    /// zeroed.
+    backward: BackwardLbrGruConfig,
+        BackwardLbrGru,
+        dnnl_lbr_gru_backward_primitive_desc_create,
+}
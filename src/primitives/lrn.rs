@@ -0,0 +1,158 @@
+use {
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::MemoryDescriptor,
+        primitive::{
+            attributes::PrimitiveAttributes, config::PrimitiveConfig,
+            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
+            PropBackwardData, PropForwardTraining, PropType,
+        },
+    },
+    onednnl_sys::{
+        dnnl_alg_kind_t, dnnl_dim_t, dnnl_lrn_backward_primitive_desc_create,
+        dnnl_lrn_forward_primitive_desc_create, dnnl_status_t,
+    },
+    std::{marker::PhantomData, sync::Arc},
+};
+
+/// Configuration for a forward local response normalization.
+///
+/// Each value is divided by `(k + alpha / n * sum) ^ beta`, where `sum` adds up the
+/// squares of the values in a window of `local_size` around it. With
+/// [`Lrn::ACROSS_CHANNELS`] the window runs over neighbouring channels and `n` is
+/// `local_size`; with [`Lrn::WITHIN_CHANNEL`] it is a `local_size` wide square over the
+/// spatial dimensions and `n` is the number of values in that square.
+///
+/// Created with `PropForwardTraining`, some implementations write a workspace for the
+/// backward pass. Query it with [`PrimitiveDescriptor::workspace_desc`] and, if there
+/// is one, pass the same memory as `DNNL_ARG_WORKSPACE` to the forward and the backward
+/// primitive.
+pub struct ForwardLrnConfig {
+    pub alg_kind: dnnl_alg_kind_t::Type,
+    pub src_desc: MemoryDescriptor,
+    pub dst_desc: MemoryDescriptor,
+    pub local_size: dnnl_dim_t,
+    pub alpha: f32,
+    pub beta: f32,
+    pub k: f32,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardLrnConfig {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Forward, P, ForwardLrnConfig>, DnnlError> {
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_lrn_forward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.alg_kind,
+                self.src_desc.handle,
+                self.dst_desc.handle,
+                self.local_size,
+                self.alpha,
+                self.beta,
+                self.k,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+/// Configuration for a backward local response normalization.
+///
+/// The parameters must match the forward normalization in `hint_fwd_pd`. The gradient
+/// is computed from the forward input, so executing it takes `DNNL_ARG_SRC` alongside
+/// `DNNL_ARG_DIFF_DST`, plus the forward workspace as `DNNL_ARG_WORKSPACE` if it wrote
+/// one.
+pub struct BackwardLrnConfig<'a> {
+    pub alg_kind: dnnl_alg_kind_t::Type,
+    pub diff_src_desc: MemoryDescriptor,
+    pub diff_dst_desc: MemoryDescriptor,
+    pub src_desc: MemoryDescriptor,
+    pub local_size: dnnl_dim_t,
+    pub alpha: f32,
+    pub beta: f32,
+    pub k: f32,
+    pub hint_fwd_pd: &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardLrnConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a> PrimitiveConfig<'a, Backward, PropBackwardData> for BackwardLrnConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Backward, PropBackwardData, BackwardLrnConfig<'a>>, DnnlError>
+    {
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_lrn_backward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                self.alg_kind,
+                self.diff_src_desc.handle,
+                self.diff_dst_desc.handle,
+                self.src_desc.handle,
+                self.local_size,
+                self.alpha,
+                self.beta,
+                self.k,
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+pub struct Lrn;
+
+impl Lrn {
+    pub const ACROSS_CHANNELS: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_lrn_across_channels;
+    pub const WITHIN_CHANNEL: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_lrn_within_channel;
+}
+
+pub struct ForwardLrn<P: PropType<Forward>> {
+    pub prop_type: P,
+}
+
+impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardLrn<P> {
+    const TYPE: OperationType = OperationType::Lrn;
+    type OperationConfig = ForwardLrnConfig;
+}
+
+pub struct BackwardLrn;
+
+impl<'a> Operation<'a, Backward, PropBackwardData> for BackwardLrn {
+    const TYPE: OperationType = OperationType::Lrn;
+    type OperationConfig = BackwardLrnConfig<'a>;
+}
@@ -0,0 +1,323 @@
+use {
+    super::rnn::{check_dims, check_optional_diff, optional_handle, RnnDescs, RnnDirection},
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::MemoryDescriptor,
+        primitive::{
+            attributes::PrimitiveAttributes, config::PrimitiveConfig,
+            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
+            PropForwardTraining, PropType,
+        },
+    },
+    onednnl_sys::{
+        dnnl_lstm_backward_primitive_desc_create, dnnl_lstm_forward_primitive_desc_create,
+        dnnl_status_t,
+    },
+    std::{ffi::c_uint, marker::PhantomData, sync::Arc},
+};
+
+/// Configuration for a forward LSTM.
+///
+/// With `T` time steps, batch `N`, `L` layers, `D` directions, `SLC` input channels,
+/// `DHC` hidden channels and `DIC` output channels, the descriptors have the shapes
+///
+/// * `src_layer_desc`: `[T, N, SLC]`
+/// * `src_iter_desc`, `dst_iter_desc`: `[L, D, N, DIC]`
+/// * `src_iter_c_desc`, `dst_iter_c_desc`: `[L, D, N, DHC]`
+/// * `weights_layer_desc`: `[L, D, SLC, 4, DHC]`
+/// * `weights_iter_desc`: `[L, D, DIC, 4, DHC]`
+/// * `weights_peephole_desc`: `[L, D, 3, DHC]`
+/// * `weights_projection_desc`: `[L, D, DHC, DIC]`
+/// * `bias_desc`: `[L, D, 4, DHC]`
+/// * `dst_layer_desc`: `[T, N, DIC]`, or `[T, N, 2 * DIC]` for
+///   `RnnDirection::BidirectionalConcat`
+///
+/// The gates are ordered input, forget, candidate, output, and the peephole weights
+/// apply to the input, forget and output gates. Without projection `DIC` equals `DHC`.
+/// The optional descriptors may be `None`, in which case the initial states are zero,
+/// there are no peephole, projection or bias weights, or the final states are not
+/// written. The shapes are checked against each other when the primitive descriptor is
+/// created.
+pub struct ForwardLstmConfig {
+    pub direction: RnnDirection,
+    pub src_layer_desc: MemoryDescriptor,
+    pub src_iter_desc: Option<MemoryDescriptor>,
+    pub src_iter_c_desc: Option<MemoryDescriptor>,
+    pub weights_layer_desc: MemoryDescriptor,
+    pub weights_iter_desc: MemoryDescriptor,
+    pub weights_peephole_desc: Option<MemoryDescriptor>,
+    pub weights_projection_desc: Option<MemoryDescriptor>,
+    pub bias_desc: Option<MemoryDescriptor>,
+    pub dst_layer_desc: MemoryDescriptor,
+    pub dst_iter_desc: Option<MemoryDescriptor>,
+    pub dst_iter_c_desc: Option<MemoryDescriptor>,
+    pub flags: c_uint,
+    pub attr: PrimitiveAttributes,
+}
+
+impl ForwardLstmConfig {
+    fn check_shapes(&self) -> Result<(), DnnlError> {
+        let descs = RnnDescs {
+            direction: self.direction,
+            src_layer: &self.src_layer_desc,
+            src_iter: self.src_iter_desc.as_ref(),
+            weights_layer: &self.weights_layer_desc,
+            weights_iter: &self.weights_iter_desc,
+            bias: self.bias_desc.as_ref(),
+            dst_layer: &self.dst_layer_desc,
+            dst_iter: self.dst_iter_desc.as_ref(),
+        };
+
+        check_lstm_shapes(
+            &descs,
+            LstmDescs {
+                src_iter_c: self.src_iter_c_desc.as_ref(),
+                weights_peephole: self.weights_peephole_desc.as_ref(),
+                weights_projection: self.weights_projection_desc.as_ref(),
+                dst_iter_c: self.dst_iter_c_desc.as_ref(),
+            },
+        )
+    }
+}
+
+impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardLstmConfig {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Forward, P, ForwardLstmConfig>, DnnlError> {
+        self.check_shapes()?;
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_lstm_forward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.direction.into(),
+                self.src_layer_desc.handle,
+                optional_handle(&self.src_iter_desc),
+                optional_handle(&self.src_iter_c_desc),
+                self.weights_layer_desc.handle,
+                self.weights_iter_desc.handle,
+                optional_handle(&self.weights_peephole_desc),
+                optional_handle(&self.weights_projection_desc),
+                optional_handle(&self.bias_desc),
+                self.dst_layer_desc.handle,
+                optional_handle(&self.dst_iter_desc),
+                optional_handle(&self.dst_iter_c_desc),
+                self.flags,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor::<'a, Forward, P, ForwardLstmConfig> {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+/// Configuration for a backward LSTM.
+///
+/// Each `diff_*` descriptor has the shape of the forward descriptor it corresponds to.
+/// CPU implementations expect `weights_layer_desc` and `weights_iter_desc` in the
+/// `ldgoi` format and the diff weights in `ldigo`. Unless
+/// `dnnl_rnn_flags_diff_weights_overwrite` is set, the diff weights and diff bias are
+/// accumulated into, so they should start zeroed. The shapes are checked when the
+/// primitive descriptor is created.
+pub struct BackwardLstmConfig<'a> {
+    pub direction: RnnDirection,
+    pub src_layer_desc: MemoryDescriptor,
+    pub src_iter_desc: Option<MemoryDescriptor>,
+    pub src_iter_c_desc: Option<MemoryDescriptor>,
+    pub weights_layer_desc: MemoryDescriptor,
+    pub weights_iter_desc: MemoryDescriptor,
+    pub weights_peephole_desc: Option<MemoryDescriptor>,
+    pub weights_projection_desc: Option<MemoryDescriptor>,
+    pub bias_desc: Option<MemoryDescriptor>,
+    pub dst_layer_desc: MemoryDescriptor,
+    pub dst_iter_desc: Option<MemoryDescriptor>,
+    pub dst_iter_c_desc: Option<MemoryDescriptor>,
+    pub diff_src_layer_desc: MemoryDescriptor,
+    pub diff_src_iter_desc: Option<MemoryDescriptor>,
+    pub diff_src_iter_c_desc: Option<MemoryDescriptor>,
+    pub diff_weights_layer_desc: MemoryDescriptor,
+    pub diff_weights_iter_desc: MemoryDescriptor,
+    pub diff_weights_peephole_desc: Option<MemoryDescriptor>,
+    pub diff_weights_projection_desc: Option<MemoryDescriptor>,
+    pub diff_bias_desc: Option<MemoryDescriptor>,
+    pub diff_dst_layer_desc: MemoryDescriptor,
+    pub diff_dst_iter_desc: Option<MemoryDescriptor>,
+    pub diff_dst_iter_c_desc: Option<MemoryDescriptor>,
+    pub flags: c_uint,
+    pub hint_fwd_pd: &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardLstmConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl BackwardLstmConfig<'_> {
+    fn check_shapes(&self) -> Result<(), DnnlError> {
+        let descs = RnnDescs {
+            direction: self.direction,
+            src_layer: &self.src_layer_desc,
+            src_iter: self.src_iter_desc.as_ref(),
+            weights_layer: &self.weights_layer_desc,
+            weights_iter: &self.weights_iter_desc,
+            bias: self.bias_desc.as_ref(),
+            dst_layer: &self.dst_layer_desc,
+            dst_iter: self.dst_iter_desc.as_ref(),
+        };
+        let diff_descs = RnnDescs {
+            direction: self.direction,
+            src_layer: &self.diff_src_layer_desc,
+            src_iter: self.diff_src_iter_desc.as_ref(),
+            weights_layer: &self.diff_weights_layer_desc,
+            weights_iter: &self.diff_weights_iter_desc,
+            bias: self.diff_bias_desc.as_ref(),
+            dst_layer: &self.diff_dst_layer_desc,
+            dst_iter: self.diff_dst_iter_desc.as_ref(),
+        };
+
+        check_lstm_shapes(
+            &descs,
+            LstmDescs {
+                src_iter_c: self.src_iter_c_desc.as_ref(),
+                weights_peephole: self.weights_peephole_desc.as_ref(),
+                weights_projection: self.weights_projection_desc.as_ref(),
+                dst_iter_c: self.dst_iter_c_desc.as_ref(),
+            },
+        )?;
+        descs.check_diffs(&diff_descs)?;
+
+        for (desc, diff_desc) in [
+            (&self.src_iter_c_desc, &self.diff_src_iter_c_desc),
+            (
+                &self.weights_peephole_desc,
+                &self.diff_weights_peephole_desc,
+            ),
+            (
+                &self.weights_projection_desc,
+                &self.diff_weights_projection_desc,
+            ),
+            (&self.dst_iter_c_desc, &self.diff_dst_iter_c_desc),
+        ] {
+            check_optional_diff(desc.as_ref(), diff_desc.as_ref())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, P: PropType<Backward>> PrimitiveConfig<'a, Backward, P> for BackwardLstmConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Backward, P, BackwardLstmConfig<'a>>, DnnlError> {
+        self.check_shapes()?;
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_lstm_backward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.direction.into(),
+                self.src_layer_desc.handle,
+                optional_handle(&self.src_iter_desc),
+                optional_handle(&self.src_iter_c_desc),
+                self.weights_layer_desc.handle,
+                self.weights_iter_desc.handle,
+                optional_handle(&self.weights_peephole_desc),
+                optional_handle(&self.weights_projection_desc),
+                optional_handle(&self.bias_desc),
+                self.dst_layer_desc.handle,
+                optional_handle(&self.dst_iter_desc),
+                optional_handle(&self.dst_iter_c_desc),
+                self.diff_src_layer_desc.handle,
+                optional_handle(&self.diff_src_iter_desc),
+                optional_handle(&self.diff_src_iter_c_desc),
+                self.diff_weights_layer_desc.handle,
+                self.diff_weights_iter_desc.handle,
+                optional_handle(&self.diff_weights_peephole_desc),
+                optional_handle(&self.diff_weights_projection_desc),
+                optional_handle(&self.diff_bias_desc),
+                self.diff_dst_layer_desc.handle,
+                optional_handle(&self.diff_dst_iter_desc),
+                optional_handle(&self.diff_dst_iter_c_desc),
+                self.flags,
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+/// The descriptors only LSTM has on top of [`RnnDescs`].
+struct LstmDescs<'d> {
+    src_iter_c: Option<&'d MemoryDescriptor>,
+    weights_peephole: Option<&'d MemoryDescriptor>,
+    weights_projection: Option<&'d MemoryDescriptor>,
+    dst_iter_c: Option<&'d MemoryDescriptor>,
+}
+
+/// Checks the shared RNN descriptors for a four gate cell, and the cell states, peephole
+/// and projection weights against them. Without projection, `DIC` has to equal `DHC`.
+fn check_lstm_shapes(descs: &RnnDescs<'_>, lstm_descs: LstmDescs<'_>) -> Result<(), DnnlError> {
+    let dims = descs.check_projected(4, 4)?;
+    let directions = descs.direction.directions();
+
+    if let Some(desc) = lstm_descs.src_iter_c {
+        check_dims(desc, &[dims.l, directions, dims.n, dims.dhc])?;
+    }
+    if let Some(desc) = lstm_descs.dst_iter_c {
+        check_dims(desc, &[dims.l, directions, dims.n, dims.dhc])?;
+    }
+    if let Some(desc) = lstm_descs.weights_peephole {
+        check_dims(desc, &[dims.l, directions, 3, dims.dhc])?;
+    }
+
+    match lstm_descs.weights_projection {
+        Some(desc) => check_dims(desc, &[dims.l, directions, dims.dhc, dims.dic]),
+        None if dims.dic != dims.dhc => Err(DnnlError::InvalidShape),
+        None => Ok(()),
+    }
+}
+
+pub struct ForwardLstm<P: PropType<Forward>> {
+    pub prop_type: P,
+}
+
+impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardLstm<P> {
+    const TYPE: OperationType = OperationType::Lstm;
+    type OperationConfig = ForwardLstmConfig;
+}
+
+pub struct BackwardLstm<P: PropType<Backward>> {
+    pub prop_type: P,
+}
+
+impl<'a, P: PropType<Backward>> Operation<'a, Backward, P> for BackwardLstm<P> {
+    const TYPE: OperationType = OperationType::Lstm;
+    type OperationConfig = BackwardLstmConfig<'a>;
+}
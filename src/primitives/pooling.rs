@@ -0,0 +1,188 @@
+use {
+    super::check_spatial_dims,
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::MemoryDescriptor,
+        primitive::{
+            attributes::PrimitiveAttributes, config::PrimitiveConfig,
+            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
+            PropBackwardData, PropForwardTraining, PropType,
+        },
+    },
+    onednnl_sys::{
+        dnnl_alg_kind_t, dnnl_dim_t, dnnl_pooling_backward_primitive_desc_create,
+        dnnl_pooling_forward_primitive_desc_create, dnnl_status_t,
+    },
+    std::{marker::PhantomData, sync::Arc},
+};
+
+/// Configuration for a forward pooling.
+///
+/// `strides`, `kernel`, `dilation`, `padding_l` and `padding_r` hold one entry per
+/// spatial dimension of `src_desc`. Dilations follow the oneDNN convention, where `0`
+/// means no dilation.
+///
+/// Max pooling created with `PropForwardTraining` writes a workspace recording where
+/// each maximum came from. Query it with
+/// [`PrimitiveDescriptor::workspace_desc`] and pass the same memory as
+/// `DNNL_ARG_WORKSPACE` to the forward and the backward primitive.
+pub struct ForwardPoolingConfig {
+    pub alg_kind: dnnl_alg_kind_t::Type,
+    pub src_desc: MemoryDescriptor,
+    pub dst_desc: MemoryDescriptor,
+    pub strides: Vec<dnnl_dim_t>,
+    pub kernel: Vec<dnnl_dim_t>,
+    pub dilation: Vec<dnnl_dim_t>,
+    pub padding_l: Vec<dnnl_dim_t>,
+    pub padding_r: Vec<dnnl_dim_t>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardPoolingConfig {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Forward, P, ForwardPoolingConfig>, DnnlError> {
+        check_spatial_dims(
+            &self.src_desc,
+            &[
+                &self.strides,
+                &self.kernel,
+                &self.dilation,
+                &self.padding_l,
+                &self.padding_r,
+            ],
+        )?;
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_pooling_forward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.alg_kind,
+                self.src_desc.handle,
+                self.dst_desc.handle,
+                self.strides.as_ptr(),
+                self.kernel.as_ptr(),
+                self.dilation.as_ptr(),
+                self.padding_l.as_ptr(),
+                self.padding_r.as_ptr(),
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(
+                PrimitiveDescriptor::<'a, Forward, P, ForwardPoolingConfig> {
+                    handle,
+                    config: self,
+
+                    _marker_a: PhantomData,
+                    _marker_d: PhantomData,
+                    _marker_p: PhantomData,
+                },
+            )
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+/// Configuration for a backward pooling.
+///
+/// The pooling parameters must match the forward pooling in `hint_fwd_pd`. For max
+/// pooling, pass the workspace written by the forward primitive as
+/// `DNNL_ARG_WORKSPACE` when executing.
+pub struct BackwardPoolingConfig<'a> {
+    pub alg_kind: dnnl_alg_kind_t::Type,
+    pub diff_src_desc: MemoryDescriptor,
+    pub diff_dst_desc: MemoryDescriptor,
+    pub strides: Vec<dnnl_dim_t>,
+    pub kernel: Vec<dnnl_dim_t>,
+    pub dilation: Vec<dnnl_dim_t>,
+    pub padding_l: Vec<dnnl_dim_t>,
+    pub padding_r: Vec<dnnl_dim_t>,
+    pub hint_fwd_pd:
+        &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardPoolingConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a> PrimitiveConfig<'a, Backward, PropBackwardData> for BackwardPoolingConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<
+        PrimitiveDescriptor<'a, Backward, PropBackwardData, BackwardPoolingConfig<'a>>,
+        DnnlError,
+    > {
+        check_spatial_dims(
+            &self.diff_src_desc,
+            &[
+                &self.strides,
+                &self.kernel,
+                &self.dilation,
+                &self.padding_l,
+                &self.padding_r,
+            ],
+        )?;
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_pooling_backward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                self.alg_kind,
+                self.diff_src_desc.handle,
+                self.diff_dst_desc.handle,
+                self.strides.as_ptr(),
+                self.kernel.as_ptr(),
+                self.dilation.as_ptr(),
+                self.padding_l.as_ptr(),
+                self.padding_r.as_ptr(),
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+pub struct Pooling;
+
+impl Pooling {
+    pub const MAX: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_pooling_max;
+    pub const AVG_INCLUDE_PADDING: dnnl_alg_kind_t::Type =
+        dnnl_alg_kind_t::dnnl_pooling_avg_include_padding;
+    pub const AVG_EXCLUDE_PADDING: dnnl_alg_kind_t::Type =
+        dnnl_alg_kind_t::dnnl_pooling_avg_exclude_padding;
+}
+
+pub struct ForwardPooling<P: PropType<Forward>> {
+    pub prop_type: P,
+}
+
+impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardPooling<P> {
+    const TYPE: OperationType = OperationType::Pooling;
+    type OperationConfig = ForwardPoolingConfig;
+}
+
+pub struct BackwardPooling;
+
+impl<'a> Operation<'a, Backward, PropBackwardData> for BackwardPooling {
+    const TYPE: OperationType = OperationType::Pooling;
+    type OperationConfig = BackwardPoolingConfig<'a>;
+}
@@ -1,10 +1,14 @@
 use {
     crate::{
         memory::descriptor::MemoryDescriptor,
-        onednnl_sys::{dnnl_prelu_forward_primitive_desc_create, dnnl_status_t},
+        onednnl_sys::{
+            dnnl_prelu_backward_primitive_desc_create, dnnl_prelu_forward_primitive_desc_create,
+            dnnl_status_t,
+        },
         primitive::{
             attributes::PrimitiveAttributes, config::PrimitiveConfig,
-            descriptor::PrimitiveDescriptor, Forward, Operation, OperationType, PropType,
+            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
+            PropBackward, PropForwardTraining, PropType,
         },
     },
     std::marker::PhantomData,
@@ -53,6 +57,61 @@ impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardPreluC
     }
 }
 
+/// Configuration for a backward PReLU, which computes the gradients of both the
+/// source and the slopes.
+///
+/// Executing it takes `DNNL_ARG_SRC`, `DNNL_ARG_WEIGHTS` and `DNNL_ARG_DIFF_DST`, and
+/// writes `DNNL_ARG_DIFF_SRC` and `DNNL_ARG_DIFF_WEIGHTS`. `diff_weights_desc` has the
+/// shape of `weights_desc`, so a slope broadcast over several values receives the sum of
+/// their gradients.
+pub struct BackwardPreluConfig<'a> {
+    pub src_desc: MemoryDescriptor,
+    pub weights_desc: MemoryDescriptor,
+    pub diff_src_desc: MemoryDescriptor,
+    pub diff_weights_desc: MemoryDescriptor,
+    pub diff_dst_desc: MemoryDescriptor,
+    pub hint_fwd_pd: &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardPreluConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a> PrimitiveConfig<'a, Backward, PropBackward> for BackwardPreluConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: std::sync::Arc<crate::engine::Engine>,
+    ) -> Result<
+        PrimitiveDescriptor<'a, Backward, PropBackward, BackwardPreluConfig<'a>>,
+        crate::error::DnnlError,
+    > {
+        let mut handle = std::ptr::null_mut();
+
+        let status = unsafe {
+            dnnl_prelu_backward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                self.src_desc.handle,
+                self.weights_desc.handle,
+                self.diff_src_desc.handle,
+                self.diff_weights_desc.handle,
+                self.diff_dst_desc.handle,
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
 pub struct ForwardPrelu<P: PropType<Forward>> {
     pub prop_type: P,
 }
@@ -61,3 +120,10 @@ impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardPrelu<P> {
     const TYPE: crate::primitive::OperationType = OperationType::PRelu;
     type OperationConfig = ForwardPreluConfig;
 }
+
+pub struct BackwardPrelu;
+
+impl<'a> Operation<'a, Backward, PropBackward> for BackwardPrelu {
+    const TYPE: OperationType = OperationType::PRelu;
+    type OperationConfig = BackwardPreluConfig<'a>;
+}
@@ -0,0 +1,75 @@
+use {
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::MemoryDescriptor,
+        primitive::{
+            attributes::PrimitiveAttributes, config::PrimitiveConfig,
+            descriptor::PrimitiveDescriptor, Forward, Operation, OperationType, PropAny,
+        },
+    },
+    onednnl_sys::{dnnl_reorder_primitive_desc_create, dnnl_status_t},
+    std::{marker::PhantomData, sync::Arc},
+};
+
+/// Configuration for a reorder, which copies `src_desc` into `dst_desc`.
+///
+/// The two descriptors have to share their dimensions, but can differ in layout and
+/// data type, so a reorder both converts between formats (e.g. `abcd` to `acdb`) and
+/// quantizes (e.g. `f32` to `s8`). Scales set on `attr` with
+/// [`PrimitiveAttributes::set_scales_mask`] are passed as
+/// `DNNL_ARG_ATTR_SCALES | DNNL_ARG_SRC` or `DNNL_ARG_ATTR_SCALES | DNNL_ARG_DST`.
+///
+/// `src_engine` and `dst_engine` default to the engine the primitive descriptor is
+/// created on; setting them to different engines copies between devices. Reorder has
+/// no propagation kind, so it is created with [`PropAny`]. Pass the memories as
+/// `DNNL_ARG_FROM` and `DNNL_ARG_TO`.
+pub struct ForwardReorderConfig {
+    pub src_desc: MemoryDescriptor,
+    pub src_engine: Option<Arc<Engine>>,
+    pub dst_desc: MemoryDescriptor,
+    pub dst_engine: Option<Arc<Engine>>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a> PrimitiveConfig<'a, Forward, PropAny> for ForwardReorderConfig {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Forward, PropAny, ForwardReorderConfig>, DnnlError> {
+        let src_engine = self.src_engine.as_ref().unwrap_or(&engine);
+        let dst_engine = self.dst_engine.as_ref().unwrap_or(&engine);
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_reorder_primitive_desc_create(
+                &mut handle,
+                self.src_desc.handle,
+                src_engine.handle,
+                self.dst_desc.handle,
+                dst_engine.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+pub struct ForwardReorder;
+
+impl<'a> Operation<'a, Forward, PropAny> for ForwardReorder {
+    const TYPE: OperationType = OperationType::Reorder;
+    type OperationConfig = ForwardReorderConfig;
+}
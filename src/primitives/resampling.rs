@@ -0,0 +1,163 @@
+use {
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::{MemoryDescriptor, NDimsQuery},
+        primitive::{
+            attributes::PrimitiveAttributes, config::PrimitiveConfig,
+            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
+            PropBackwardData, PropForwardTraining, PropType,
+        },
+    },
+    onednnl_sys::{
+        dnnl_alg_kind_t, dnnl_resampling_backward_primitive_desc_create,
+        dnnl_resampling_forward_primitive_desc_create, dnnl_status_t,
+    },
+    std::{marker::PhantomData, sync::Arc},
+};
+
+/// Factors are read as an array with one entry per spatial dimension of `src_desc`.
+fn check_factors(src_desc: &MemoryDescriptor, factors: Option<&[f32]>) -> Result<(), DnnlError> {
+    let spatial_dims = (src_desc.query::<NDimsQuery>()? - 2).max(0) as usize;
+
+    match factors {
+        Some(factors) if factors.len() != spatial_dims => Err(DnnlError::InvalidArguments),
+        _ => Ok(()),
+    }
+}
+
+/// Configuration for a forward resampling.
+///
+/// The output size is given either by `factors`, one per spatial dimension of
+/// `src_desc` (`2.0` doubles the height and width of an NCHW tensor), or by
+/// `dst_desc`. With only `factors`, oneDNN works out the destination, which can be
+/// read back with `query_md(dnnl_query_dst_md, 0)` on the primitive descriptor. At
+/// least one of the two has to be set.
+pub struct ForwardResamplingConfig {
+    pub alg_kind: dnnl_alg_kind_t::Type,
+    pub factors: Option<Vec<f32>>,
+    pub src_desc: MemoryDescriptor,
+    pub dst_desc: Option<MemoryDescriptor>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardResamplingConfig {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Forward, P, ForwardResamplingConfig>, DnnlError> {
+        if self.factors.is_none() && self.dst_desc.is_none() {
+            return Err(DnnlError::InvalidArguments);
+        }
+        check_factors(&self.src_desc, self.factors.as_deref())?;
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_resampling_forward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.alg_kind,
+                self.factors
+                    .as_ref()
+                    .map_or(std::ptr::null(), |factors| factors.as_ptr()),
+                self.src_desc.handle,
+                self.dst_desc
+                    .as_ref()
+                    .map_or(std::ptr::null(), |desc| desc.handle),
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+/// Configuration for a backward resampling.
+///
+/// Both gradients need a descriptor. `factors` can be left as `None`, in which case
+/// oneDNN derives them from the ratio of `diff_dst_desc` to `diff_src_desc`.
+pub struct BackwardResamplingConfig<'a> {
+    pub alg_kind: dnnl_alg_kind_t::Type,
+    pub factors: Option<Vec<f32>>,
+    pub diff_src_desc: MemoryDescriptor,
+    pub diff_dst_desc: MemoryDescriptor,
+    pub hint_fwd_pd:
+        &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardResamplingConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a> PrimitiveConfig<'a, Backward, PropBackwardData> for BackwardResamplingConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<
+        PrimitiveDescriptor<'a, Backward, PropBackwardData, BackwardResamplingConfig<'a>>,
+        DnnlError,
+    > {
+        check_factors(&self.diff_src_desc, self.factors.as_deref())?;
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_resampling_backward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                self.alg_kind,
+                self.factors
+                    .as_ref()
+                    .map_or(std::ptr::null(), |factors| factors.as_ptr()),
+                self.diff_src_desc.handle,
+                self.diff_dst_desc.handle,
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+pub struct Resampling;
+
+impl Resampling {
+    pub const NEAREST: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_resampling_nearest;
+    pub const LINEAR: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_resampling_linear;
+}
+
+pub struct ForwardResampling<P: PropType<Forward>> {
+    pub prop_type: P,
+}
+
+impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardResampling<P> {
+    const TYPE: OperationType = OperationType::Resampling;
+    type OperationConfig = ForwardResamplingConfig;
+}
+
+pub struct BackwardResampling;
+
+impl<'a> Operation<'a, Backward, PropBackwardData> for BackwardResampling {
+    const TYPE: OperationType = OperationType::Resampling;
+    type OperationConfig = BackwardResamplingConfig<'a>;
+}
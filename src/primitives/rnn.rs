@@ -0,0 +1,179 @@
+use {
+    crate::{
+        error::DnnlError,
+        memory::descriptor::{DimsQuery, MemoryDescriptor},
+    },
+    onednnl_sys::{dnnl_dim_t, dnnl_memory_desc_t, dnnl_rnn_direction_t},
+};
+
+/// The order in which a recurrent primitive walks the sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RnnDirection {
+    /// Process the sequence from the first to the last time step.
+    LeftToRight,
+    /// Process the sequence from the last to the first time step.
+    RightToLeft,
+    /// Run both directions and concatenate their outputs along the channels.
+    BidirectionalConcat,
+    /// Run both directions and sum their outputs.
+    BidirectionalSum,
+}
+
+impl RnnDirection {
+    /// The number of directions, `D` in the oneDNN RNN shapes.
+    pub fn directions(self) -> dnnl_dim_t {
+        match self {
+            RnnDirection::LeftToRight | RnnDirection::RightToLeft => 1,
+            RnnDirection::BidirectionalConcat | RnnDirection::BidirectionalSum => 2,
+        }
+    }
+}
+
+impl From<RnnDirection> for dnnl_rnn_direction_t::Type {
+    fn from(direction: RnnDirection) -> Self {
+        match direction {
+            RnnDirection::LeftToRight => dnnl_rnn_direction_t::dnnl_unidirectional_left2right,
+            RnnDirection::RightToLeft => dnnl_rnn_direction_t::dnnl_unidirectional_right2left,
+            RnnDirection::BidirectionalConcat => dnnl_rnn_direction_t::dnnl_bidirectional_concat,
+            RnnDirection::BidirectionalSum => dnnl_rnn_direction_t::dnnl_bidirectional_sum,
+        }
+    }
+}
+
+/// oneDNN takes a NULL descriptor for the optional RNN inputs and outputs.
+pub(crate) fn optional_handle(desc: &Option<MemoryDescriptor>) -> dnnl_memory_desc_t {
+    desc.as_ref().map_or(std::ptr::null_mut(), |d| d.handle)
+}
+
+/// The layer, iteration, weights and bias descriptors shared by the LSTM, GRU family
+/// and vanilla RNN cells.
+pub(crate) struct RnnDescs<'d> {
+    pub direction: RnnDirection,
+    pub src_layer: &'d MemoryDescriptor,
+    pub src_iter: Option<&'d MemoryDescriptor>,
+    pub weights_layer: &'d MemoryDescriptor,
+    pub weights_iter: &'d MemoryDescriptor,
+    pub bias: Option<&'d MemoryDescriptor>,
+    pub dst_layer: &'d MemoryDescriptor,
+    pub dst_iter: Option<&'d MemoryDescriptor>,
+}
+
+/// The sizes read off the descriptors by [`RnnDescs::check_projected`].
+pub(crate) struct RnnDims {
+    pub t: dnnl_dim_t,
+    pub n: dnnl_dim_t,
+    pub l: dnnl_dim_t,
+    pub dhc: dnnl_dim_t,
+    pub dic: dnnl_dim_t,
+}
+
+impl RnnDescs<'_> {
+    /// Checks the descriptors against each other for a cell with `gates` gates and
+    /// `bias_gates` bias rows, taking `T`, `N` and `SLC` from `src_layer` and `L` and
+    /// `DHC` from `weights_iter`. Returns `(T, N)` for cells with extra inputs to check.
+    pub fn check(
+        &self,
+        gates: dnnl_dim_t,
+        bias_gates: dnnl_dim_t,
+    ) -> Result<(dnnl_dim_t, dnnl_dim_t), DnnlError> {
+        let dims = self.check_projected(gates, bias_gates)?;
+
+        // Only LSTM projects its output, every other cell outputs DHC channels.
+        if dims.dic != dims.dhc {
+            return Err(DnnlError::InvalidShape);
+        }
+
+        Ok((dims.t, dims.n))
+    }
+
+    /// Like [`check`](Self::check), but for a cell whose output has `DIC` channels,
+    /// taken from `weights_iter` as `[L, D, DIC, gates, DHC]`, which need not match
+    /// `DHC`.
+    pub fn check_projected(
+        &self,
+        gates: dnnl_dim_t,
+        bias_gates: dnnl_dim_t,
+    ) -> Result<RnnDims, DnnlError> {
+        let directions = self.direction.directions();
+
+        let (t, n, slc) = match self.src_layer.query::<DimsQuery>()?[..] {
+            [t, n, slc] => (t, n, slc),
+            _ => return Err(DnnlError::InvalidShape),
+        };
+        let (l, dic, dhc) = match self.weights_iter.query::<DimsQuery>()?[..] {
+            [l, d, dic, _, dhc] if d == directions => (l, dic, dhc),
+            _ => return Err(DnnlError::InvalidShape),
+        };
+        let dlc = if self.direction == RnnDirection::BidirectionalConcat {
+            2 * dic
+        } else {
+            dic
+        };
+
+        // Layers after the first take the previous layer's output through the same
+        // weights_layer, so the channels have to line up.
+        if l > 1 && slc != dlc {
+            return Err(DnnlError::InvalidShape);
+        }
+
+        check_dims(self.weights_layer, &[l, directions, slc, gates, dhc])?;
+        check_dims(self.weights_iter, &[l, directions, dic, gates, dhc])?;
+        check_dims(self.dst_layer, &[t, n, dlc])?;
+
+        if let Some(desc) = self.src_iter {
+            check_dims(desc, &[l, directions, n, dic])?;
+        }
+        if let Some(desc) = self.bias {
+            check_dims(desc, &[l, directions, bias_gates, dhc])?;
+        }
+        if let Some(desc) = self.dst_iter {
+            check_dims(desc, &[l, directions, n, dic])?;
+        }
+
+        Ok(RnnDims { t, n, l, dhc, dic })
+    }
+
+    /// Checks that each diff descriptor has the shape of its forward counterpart, and
+    /// that optional diff descriptors are only given alongside their forward ones.
+    pub fn check_diffs(&self, diffs: &RnnDescs<'_>) -> Result<(), DnnlError> {
+        check_same_dims(self.src_layer, diffs.src_layer)?;
+        check_same_dims(self.weights_layer, diffs.weights_layer)?;
+        check_same_dims(self.weights_iter, diffs.weights_iter)?;
+        check_same_dims(self.dst_layer, diffs.dst_layer)?;
+
+        check_optional_diff(self.src_iter, diffs.src_iter)?;
+        check_optional_diff(self.bias, diffs.bias)?;
+        check_optional_diff(self.dst_iter, diffs.dst_iter)
+    }
+}
+
+/// Checks an optional diff descriptor against its optional forward descriptor, which
+/// it has to match in shape and can only be given alongside.
+pub(crate) fn check_optional_diff(
+    desc: Option<&MemoryDescriptor>,
+    diff_desc: Option<&MemoryDescriptor>,
+) -> Result<(), DnnlError> {
+    match (desc, diff_desc) {
+        (Some(desc), Some(diff_desc)) => check_same_dims(desc, diff_desc),
+        (None, Some(_)) => Err(DnnlError::InvalidShape),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn check_dims(
+    desc: &MemoryDescriptor,
+    expected: &[dnnl_dim_t],
+) -> Result<(), DnnlError> {
+    if desc.query::<DimsQuery>()? == expected {
+        Ok(())
+    } else {
+        Err(DnnlError::InvalidShape)
+    }
+}
+
+pub(crate) fn check_same_dims(
+    desc: &MemoryDescriptor,
+    other: &MemoryDescriptor,
+) -> Result<(), DnnlError> {
+    check_dims(other, &desc.query::<DimsQuery>()?)
+}
@@ -0,0 +1,133 @@
+use {
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::MemoryDescriptor,
+        primitive::{
+            attributes::PrimitiveAttributes, config::PrimitiveConfig,
+            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
+            PropBackwardData, PropForwardTraining, PropType,
+        },
+    },
+    onednnl_sys::{
+        dnnl_dim_t, dnnl_shuffle_backward_primitive_desc_create,
+        dnnl_shuffle_forward_primitive_desc_create, dnnl_status_t,
+    },
+    std::{marker::PhantomData, sync::Arc},
+};
+
+/// Configuration for a forward shuffle.
+///
+/// The `axis` dimension of `src_desc` is split into groups of `group_size` consecutive
+/// elements, and the destination takes the first element of every group, then the
+/// second, and so on: with 6 channels and a `group_size` of 2, the destination channels
+/// are source channels `0, 2, 4, 1, 3, 5`. A ShuffleNet channel shuffle with `g` groups
+/// is a `group_size` of `channels / g`. `group_size` has to divide the size of `axis`.
+pub struct ForwardShuffleConfig {
+    pub src_desc: MemoryDescriptor,
+    pub dst_desc: MemoryDescriptor,
+    pub axis: i32,
+    pub group_size: dnnl_dim_t,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardShuffleConfig {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Forward, P, ForwardShuffleConfig>, DnnlError> {
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_shuffle_forward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.src_desc.handle,
+                self.dst_desc.handle,
+                self.axis,
+                self.group_size,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+/// Configuration for a backward shuffle.
+///
+/// `axis` and `group_size` are the ones of the forward shuffle in `hint_fwd_pd`; the
+/// gradient is moved back through the inverse permutation.
+pub struct BackwardShuffleConfig<'a> {
+    pub diff_src_desc: MemoryDescriptor,
+    pub diff_dst_desc: MemoryDescriptor,
+    pub axis: i32,
+    pub group_size: dnnl_dim_t,
+    pub hint_fwd_pd:
+        &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardShuffleConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a> PrimitiveConfig<'a, Backward, PropBackwardData> for BackwardShuffleConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<
+        PrimitiveDescriptor<'a, Backward, PropBackwardData, BackwardShuffleConfig<'a>>,
+        DnnlError,
+    > {
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_shuffle_backward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                self.diff_src_desc.handle,
+                self.diff_dst_desc.handle,
+                self.axis,
+                self.group_size,
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+pub struct ForwardShuffle<P: PropType<Forward>> {
+    pub prop_type: P,
+}
+
+impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardShuffle<P> {
+    const TYPE: OperationType = OperationType::Shuffle;
+    type OperationConfig = ForwardShuffleConfig;
+}
+
+pub struct BackwardShuffle;
+
+impl<'a> Operation<'a, Backward, PropBackwardData> for BackwardShuffle {
+    const TYPE: OperationType = OperationType::Shuffle;
+    type OperationConfig = BackwardShuffleConfig<'a>;
+}
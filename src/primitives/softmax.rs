@@ -0,0 +1,140 @@
+use {
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::MemoryDescriptor,
+        primitive::{
+            attributes::PrimitiveAttributes, config::PrimitiveConfig,
+            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
+            PropBackwardData, PropForwardTraining, PropType,
+        },
+    },
+    onednnl_sys::{
+        dnnl_alg_kind_t, dnnl_softmax_backward_primitive_desc_create,
+        dnnl_softmax_forward_primitive_desc_create, dnnl_status_t,
+    },
+    std::{marker::PhantomData, sync::Arc},
+};
+
+/// Configuration for a forward softmax.
+///
+/// `axis` is the dimension of `src_desc` the softmax is normalized over.
+pub struct ForwardSoftmaxConfig {
+    pub alg_kind: dnnl_alg_kind_t::Type,
+    pub src_desc: MemoryDescriptor,
+    pub dst_desc: MemoryDescriptor,
+    pub axis: i32,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardSoftmaxConfig {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Forward, P, ForwardSoftmaxConfig>, DnnlError> {
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_softmax_forward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.alg_kind,
+                self.src_desc.handle,
+                self.dst_desc.handle,
+                self.axis,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(
+                PrimitiveDescriptor::<'a, Forward, P, ForwardSoftmaxConfig> {
+                    handle,
+                    config: self,
+
+                    _marker_a: PhantomData,
+                    _marker_d: PhantomData,
+                    _marker_p: PhantomData,
+                },
+            )
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+/// Configuration for a backward softmax.
+///
+/// The gradient is computed from the forward result, so executing it takes
+/// `DNNL_ARG_DST` alongside `DNNL_ARG_DIFF_DST`.
+pub struct BackwardSoftmaxConfig<'a> {
+    pub alg_kind: dnnl_alg_kind_t::Type,
+    pub diff_src_desc: MemoryDescriptor,
+    pub diff_dst_desc: MemoryDescriptor,
+    pub dst_desc: MemoryDescriptor,
+    pub axis: i32,
+    pub hint_fwd_pd:
+        &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardSoftmaxConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a> PrimitiveConfig<'a, Backward, PropBackwardData> for BackwardSoftmaxConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<
+        PrimitiveDescriptor<'a, Backward, PropBackwardData, BackwardSoftmaxConfig<'a>>,
+        DnnlError,
+    > {
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_softmax_backward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                self.alg_kind,
+                self.diff_src_desc.handle,
+                self.diff_dst_desc.handle,
+                self.dst_desc.handle,
+                self.axis,
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+pub struct Softmax;
+
+impl Softmax {
+    pub const ACCURATE: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_softmax_accurate;
+    pub const LOG: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_softmax_log;
+}
+
+pub struct ForwardSoftmax<P: PropType<Forward>> {
+    pub prop_type: P,
+}
+
+impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardSoftmax<P> {
+    const TYPE: OperationType = OperationType::Softmax;
+    type OperationConfig = ForwardSoftmaxConfig;
+}
+
+pub struct BackwardSoftmax;
+
+impl<'a> Operation<'a, Backward, PropBackwardData> for BackwardSoftmax {
+    const TYPE: OperationType = OperationType::Softmax;
+    type OperationConfig = BackwardSoftmaxConfig<'a>;
+}
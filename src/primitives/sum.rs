@@ -0,0 +1,81 @@
+use {
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::MemoryDescriptor,
+        primitive::{
+            attributes::PrimitiveAttributes, config::PrimitiveConfig,
+            descriptor::PrimitiveDescriptor, Forward, Operation, OperationType, PropAny,
+        },
+    },
+    onednnl_sys::{const_dnnl_memory_desc_t, dnnl_status_t, dnnl_sum_primitive_desc_create},
+    std::{ffi::c_int, marker::PhantomData, sync::Arc},
+};
+
+/// Configuration for a sum, which adds up the sources, each multiplied by its entry in
+/// `scales`, in a single pass.
+///
+/// `scales` needs one entry per source, and the sources have to share their
+/// dimensions. When `dst_desc` is `None`, oneDNN picks the destination layout, which can
+/// be read back with `query_md(dnnl_query_dst_md, 0)` on the primitive descriptor.
+///
+/// Sum has no propagation kind, so it is created with [`PropAny`]. Pass the
+/// sources as `DNNL_ARG_MULTIPLE_SRC + i` and the destination as `DNNL_ARG_DST`.
+pub struct ForwardSumConfig<'a> {
+    pub src_descs: &'a [MemoryDescriptor],
+    pub scales: &'a [f32],
+    pub dst_desc: Option<MemoryDescriptor>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl<'a> PrimitiveConfig<'a, Forward, PropAny> for ForwardSumConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Forward, PropAny, ForwardSumConfig<'a>>, DnnlError> {
+        if self.src_descs.is_empty() || self.scales.len() != self.src_descs.len() {
+            return Err(DnnlError::InvalidArguments);
+        }
+
+        let src_handles: Vec<const_dnnl_memory_desc_t> = self
+            .src_descs
+            .iter()
+            .map(|desc| desc.handle as const_dnnl_memory_desc_t)
+            .collect();
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_sum_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                self.dst_desc
+                    .as_ref()
+                    .map_or(std::ptr::null(), |desc| desc.handle),
+                src_handles.len() as c_int,
+                self.scales.as_ptr(),
+                src_handles.as_ptr(),
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+pub struct ForwardSum;
+
+impl<'a> Operation<'a, Forward, PropAny> for ForwardSum {
+    const TYPE: OperationType = OperationType::Sum;
+    type OperationConfig = ForwardSumConfig<'a>;
+}
@@ -0,0 +1,259 @@
+use {
+    super::rnn::{optional_handle, RnnDescs, RnnDirection},
+    crate::{
+        engine::Engine,
+        error::DnnlError,
+        memory::descriptor::MemoryDescriptor,
+        primitive::{
+            attributes::PrimitiveAttributes, config::PrimitiveConfig,
+            descriptor::PrimitiveDescriptor, Backward, Forward, Operation, OperationType,
+            PropForwardTraining, PropType,
+        },
+    },
+    onednnl_sys::{
+        dnnl_alg_kind_t, dnnl_status_t, dnnl_vanilla_rnn_backward_primitive_desc_create,
+        dnnl_vanilla_rnn_forward_primitive_desc_create,
+    },
+    std::{ffi::c_uint, marker::PhantomData, sync::Arc},
+};
+
+/// Configuration for a forward vanilla RNN.
+///
+/// With `T` time steps, batch `N`, `L` layers, `D` directions, `SLC` input channels and
+/// `DHC` hidden channels, the descriptors have the shapes
+///
+/// * `src_layer_desc`: `[T, N, SLC]`
+/// * `src_iter_desc`, `dst_iter_desc`: `[L, D, N, DHC]`
+/// * `weights_layer_desc`: `[L, D, SLC, 1, DHC]`
+/// * `weights_iter_desc`: `[L, D, DHC, 1, DHC]`
+/// * `bias_desc`: `[L, D, 1, DHC]`
+/// * `dst_layer_desc`: `[T, N, DHC]`, or `[T, N, 2 * DHC]` for
+///   `RnnDirection::BidirectionalConcat`
+///
+/// The cell computes `h = activation(W x + U h_prev + b)` with one of the
+/// [`VanillaRnn`] activations. `alpha` is the negative slope for `VanillaRnn::RELU`
+/// and `beta` is unused by the current activations. The optional descriptors may be
+/// `None`, in which case the initial state is zero, there is no bias, or the final
+/// state is not written. The shapes are checked against each other when the primitive
+/// descriptor is created.
+pub struct ForwardVanillaRnnConfig {
+    pub activation: dnnl_alg_kind_t::Type,
+    pub direction: RnnDirection,
+    pub src_layer_desc: MemoryDescriptor,
+    pub src_iter_desc: Option<MemoryDescriptor>,
+    pub weights_layer_desc: MemoryDescriptor,
+    pub weights_iter_desc: MemoryDescriptor,
+    pub bias_desc: Option<MemoryDescriptor>,
+    pub dst_layer_desc: MemoryDescriptor,
+    pub dst_iter_desc: Option<MemoryDescriptor>,
+    pub flags: c_uint,
+    pub alpha: f32,
+    pub beta: f32,
+    pub attr: PrimitiveAttributes,
+}
+
+impl ForwardVanillaRnnConfig {
+    fn check_shapes(&self) -> Result<(), DnnlError> {
+        let descs = RnnDescs {
+            direction: self.direction,
+            src_layer: &self.src_layer_desc,
+            src_iter: self.src_iter_desc.as_ref(),
+            weights_layer: &self.weights_layer_desc,
+            weights_iter: &self.weights_iter_desc,
+            bias: self.bias_desc.as_ref(),
+            dst_layer: &self.dst_layer_desc,
+            dst_iter: self.dst_iter_desc.as_ref(),
+        };
+
+        descs.check(1, 1)?;
+
+        Ok(())
+    }
+}
+
+impl<'a, P: PropType<Forward>> PrimitiveConfig<'a, Forward, P> for ForwardVanillaRnnConfig {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Forward, P, ForwardVanillaRnnConfig>, DnnlError> {
+        self.check_shapes()?;
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_vanilla_rnn_forward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.activation,
+                self.direction.into(),
+                self.src_layer_desc.handle,
+                optional_handle(&self.src_iter_desc),
+                self.weights_layer_desc.handle,
+                self.weights_iter_desc.handle,
+                optional_handle(&self.bias_desc),
+                self.dst_layer_desc.handle,
+                optional_handle(&self.dst_iter_desc),
+                self.flags,
+                self.alpha,
+                self.beta,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(
+                PrimitiveDescriptor::<'a, Forward, P, ForwardVanillaRnnConfig> {
+                    handle,
+                    config: self,
+
+                    _marker_a: PhantomData,
+                    _marker_d: PhantomData,
+                    _marker_p: PhantomData,
+                },
+            )
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+/// Configuration for a backward vanilla RNN.
+///
+/// The activation, `alpha` and `beta` should match the forward configuration. Each
+/// `diff_*` descriptor has the shape of the forward descriptor it corresponds to,
+/// and an optional one may only be set when its forward descriptor is. CPU
+/// implementations expect `weights_layer_desc` and `weights_iter_desc` in the `ldgoi`
+/// format and the diff weights in `ldigo`. Unless `dnnl_rnn_flags_diff_weights_overwrite`
+/// is set, the diff weights and diff bias are accumulated into, so they should start
+/// zeroed.
+pub struct BackwardVanillaRnnConfig<'a> {
+    pub activation: dnnl_alg_kind_t::Type,
+    pub direction: RnnDirection,
+    pub src_layer_desc: MemoryDescriptor,
+    pub src_iter_desc: Option<MemoryDescriptor>,
+    pub weights_layer_desc: MemoryDescriptor,
+    pub weights_iter_desc: MemoryDescriptor,
+    pub bias_desc: Option<MemoryDescriptor>,
+    pub dst_layer_desc: MemoryDescriptor,
+    pub dst_iter_desc: Option<MemoryDescriptor>,
+    pub diff_src_layer_desc: MemoryDescriptor,
+    pub diff_src_iter_desc: Option<MemoryDescriptor>,
+    pub diff_weights_layer_desc: MemoryDescriptor,
+    pub diff_weights_iter_desc: MemoryDescriptor,
+    pub diff_bias_desc: Option<MemoryDescriptor>,
+    pub diff_dst_layer_desc: MemoryDescriptor,
+    pub diff_dst_iter_desc: Option<MemoryDescriptor>,
+    pub flags: c_uint,
+    pub alpha: f32,
+    pub beta: f32,
+    pub hint_fwd_pd:
+        &'a PrimitiveDescriptor<'a, Forward, PropForwardTraining, ForwardVanillaRnnConfig>,
+    pub attr: PrimitiveAttributes,
+}
+
+impl BackwardVanillaRnnConfig<'_> {
+    fn check_shapes(&self) -> Result<(), DnnlError> {
+        let descs = RnnDescs {
+            direction: self.direction,
+            src_layer: &self.src_layer_desc,
+            src_iter: self.src_iter_desc.as_ref(),
+            weights_layer: &self.weights_layer_desc,
+            weights_iter: &self.weights_iter_desc,
+            bias: self.bias_desc.as_ref(),
+            dst_layer: &self.dst_layer_desc,
+            dst_iter: self.dst_iter_desc.as_ref(),
+        };
+        let diff_descs = RnnDescs {
+            direction: self.direction,
+            src_layer: &self.diff_src_layer_desc,
+            src_iter: self.diff_src_iter_desc.as_ref(),
+            weights_layer: &self.diff_weights_layer_desc,
+            weights_iter: &self.diff_weights_iter_desc,
+            bias: self.diff_bias_desc.as_ref(),
+            dst_layer: &self.diff_dst_layer_desc,
+            dst_iter: self.diff_dst_iter_desc.as_ref(),
+        };
+
+        descs.check(1, 1)?;
+        descs.check_diffs(&diff_descs)
+    }
+}
+
+impl<'a, P: PropType<Backward>> PrimitiveConfig<'a, Backward, P> for BackwardVanillaRnnConfig<'a> {
+    fn create_primitive_desc(
+        self,
+        engine: Arc<Engine>,
+    ) -> Result<PrimitiveDescriptor<'a, Backward, P, BackwardVanillaRnnConfig<'a>>, DnnlError> {
+        self.check_shapes()?;
+
+        let mut handle = std::ptr::null_mut();
+        let status = unsafe {
+            dnnl_vanilla_rnn_backward_primitive_desc_create(
+                &mut handle,
+                engine.handle,
+                P::KIND,
+                self.activation,
+                self.direction.into(),
+                self.src_layer_desc.handle,
+                optional_handle(&self.src_iter_desc),
+                self.weights_layer_desc.handle,
+                self.weights_iter_desc.handle,
+                optional_handle(&self.bias_desc),
+                self.dst_layer_desc.handle,
+                optional_handle(&self.dst_iter_desc),
+                self.diff_src_layer_desc.handle,
+                optional_handle(&self.diff_src_iter_desc),
+                self.diff_weights_layer_desc.handle,
+                self.diff_weights_iter_desc.handle,
+                optional_handle(&self.diff_bias_desc),
+                self.diff_dst_layer_desc.handle,
+                optional_handle(&self.diff_dst_iter_desc),
+                self.flags,
+                self.alpha,
+                self.beta,
+                self.hint_fwd_pd.handle,
+                self.attr.handle,
+            )
+        };
+
+        if status == dnnl_status_t::dnnl_success {
+            Ok(PrimitiveDescriptor {
+                handle,
+                config: self,
+
+                _marker_a: PhantomData,
+                _marker_d: PhantomData,
+                _marker_p: PhantomData,
+            })
+        } else {
+            Err(status.into())
+        }
+    }
+}
+
+/// Activations for [`ForwardVanillaRnnConfig::activation`].
+pub struct VanillaRnn;
+
+impl VanillaRnn {
+    pub const RELU: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_eltwise_relu;
+    pub const TANH: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_eltwise_tanh;
+    pub const LOGISTIC: dnnl_alg_kind_t::Type = dnnl_alg_kind_t::dnnl_eltwise_logistic;
+}
+
+pub struct ForwardVanillaRnn<P: PropType<Forward>> {
+    pub prop_type: P,
+}
+
+impl<P: PropType<Forward>> Operation<'_, Forward, P> for ForwardVanillaRnn<P> {
+    const TYPE: OperationType = OperationType::VanillaRnn;
+    type OperationConfig = ForwardVanillaRnnConfig;
+}
+
+pub struct BackwardVanillaRnn<P: PropType<Backward>> {
+    pub prop_type: P,
+}
+
+impl<'a, P: PropType<Backward>> Operation<'a, Backward, P> for BackwardVanillaRnn<P> {
+    const TYPE: OperationType = OperationType::VanillaRnn;
+    type OperationConfig = BackwardVanillaRnnConfig<'a>;
+}
@@ -0,0 +1,152 @@
+//! The single layer the AUGRU and linear-before-reset AUGRU tests run, and the reference
+//! they are checked against.
+
+use {
+    super::sigmoid,
+    onednnl::memory::descriptor::{new_plain_descriptor, DataType, MemoryDescriptor},
+};
+
+// Two time steps, a batch of one and two channels in and out of a single
+// left-to-right layer.
+pub const T: usize = 2;
+pub const N: usize = 1;
+pub const C: usize = 2;
+pub const G: usize = 3;
+
+#[derive(Clone)]
+pub struct Params {
+    pub src_layer: Vec<f64>,
+    pub attention: Vec<f64>,
+    pub weights_layer: Vec<f64>,
+    pub weights_iter: Vec<f64>,
+    pub bias: Vec<f64>,
+}
+
+impl Params {
+    /// Parameters for a cell with `bias_gates` rows of bias.
+    pub fn new(bias_gates: usize) -> Self {
+        Params {
+            src_layer: vec![0.5, -0.3, 0.2, 0.8],
+            attention: vec![0.3, 0.6],
+            weights_layer: (0..C * G * C)
+                .map(|k| ((k * 7 % 11) as f64 - 5.0) / 10.0)
+                .collect(),
+            weights_iter: (0..C * G * C)
+                .map(|k| ((k * 5 % 13) as f64 - 6.0) / 10.0)
+                .collect(),
+            bias: (0..bias_gates * C)
+                .map(|k| ((k * 3 % 7) as f64 - 3.0) / 10.0)
+                .collect(),
+        }
+    }
+}
+
+/// AUGRU as documented by oneDNN, with weights in `ldigo` and gates ordered update,
+/// reset, output:
+///
+///   u = sigmoid(W_u x + U_u h + b_u)
+///   r = sigmoid(W_r x + U_r h + b_r)
+///   o = tanh(W_o x + U_o (r * h) + b_o)
+///   u' = (1 - a) * u
+///   h' = u' * h + (1 - u') * o
+///
+/// With `linear_before_reset`, the fourth bias row is added to the hidden product
+/// before the reset:
+///   o = tanh(W_o x + b_o + r * (U_o h + b_o'))
+pub fn reference_forward(p: &Params, linear_before_reset: bool) -> Vec<f64> {
+    let gate = |w: &[f64], input: &[f64], g: usize, o: usize| -> f64 {
+        (0..C).map(|i| input[i] * w[(i * G + g) * C + o]).sum()
+    };
+
+    let mut h = vec![0.0; N * C];
+    let mut dst = Vec::with_capacity(T * N * C);
+
+    for t in 0..T {
+        let mut next = vec![0.0; N * C];
+
+        for n in 0..N {
+            let x = &p.src_layer[(t * N + n) * C..][..C];
+            let h_prev = &h[n * C..][..C];
+            let a = p.attention[t * N + n];
+
+            let u: Vec<f64> = (0..C)
+                .map(|o| {
+                    sigmoid(
+                        gate(&p.weights_layer, x, 0, o)
+                            + gate(&p.weights_iter, h_prev, 0, o)
+                            + p.bias[o],
+                    )
+                })
+                .collect();
+            let r: Vec<f64> = (0..C)
+                .map(|o| {
+                    sigmoid(
+                        gate(&p.weights_layer, x, 1, o)
+                            + gate(&p.weights_iter, h_prev, 1, o)
+                            + p.bias[C + o],
+                    )
+                })
+                .collect();
+            let r_h: Vec<f64> = (0..C).map(|o| r[o] * h_prev[o]).collect();
+
+            for o in 0..C {
+                let candidate = if linear_before_reset {
+                    gate(&p.weights_layer, x, 2, o)
+                        + p.bias[2 * C + o]
+                        + r[o] * (gate(&p.weights_iter, h_prev, 2, o) + p.bias[3 * C + o])
+                } else {
+                    gate(&p.weights_layer, x, 2, o)
+                        + gate(&p.weights_iter, &r_h, 2, o)
+                        + p.bias[2 * C + o]
+                }
+                .tanh();
+                let u_att = (1.0 - a) * u[o];
+
+                next[n * C + o] = u_att * h_prev[o] + (1.0 - u_att) * candidate;
+            }
+        }
+
+        dst.extend_from_slice(&next);
+        h = next;
+    }
+
+    dst
+}
+
+/// Lays `ldigo` weights with a single layer and direction out as `ldgoi`.
+pub fn ldigo_to_ldgoi(weights: &[f32], input_channels: usize) -> Vec<f32> {
+    let mut out = vec![0.0; weights.len()];
+    for i in 0..input_channels {
+        for g in 0..G {
+            for o in 0..C {
+                out[(g * C + o) * input_channels + i] = weights[(i * G + g) * C + o];
+            }
+        }
+    }
+    out
+}
+
+pub struct Descs {
+    pub src_layer: MemoryDescriptor,
+    pub attention: MemoryDescriptor,
+    pub weights_layer: MemoryDescriptor,
+    pub weights_iter: MemoryDescriptor,
+    pub bias: MemoryDescriptor,
+    pub dst_layer: MemoryDescriptor,
+}
+
+impl Descs {
+    /// Descriptors for a cell with `bias_gates` rows of bias.
+    pub fn new(bias_gates: usize) -> Self {
+        let (t, n, c, g) = (T as i64, N as i64, C as i64, G as i64);
+
+        Descs {
+            src_layer: new_plain_descriptor(3, vec![t, n, c], DataType::F32),
+            attention: new_plain_descriptor(3, vec![t, n, 1], DataType::F32),
+            weights_layer: new_plain_descriptor(5, vec![1, 1, c, g, c], DataType::F32),
+            weights_iter: new_plain_descriptor(5, vec![1, 1, c, g, c], DataType::F32),
+            bias: new_plain_descriptor(4, vec![1, 1, bias_gates as i64, c], DataType::F32),
+            dst_layer: new_plain_descriptor(3, vec![t, n, c], DataType::F32),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Helpers shared by the integration tests, pulled in with `mod common;`. Each test
+//! uses only some of them.
+#![allow(dead_code)]
+
+pub mod augru;
+
+/// Asserts that `actual` and `expected` have the same length and differ by less than
+/// `tolerance` everywhere.
+pub fn assert_close<E: Copy + Into<f64> + std::fmt::Debug>(
+    actual: &[f32],
+    expected: &[E],
+    tolerance: f64,
+) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!(
+            (*a as f64 - (*e).into()).abs() < tolerance,
+            "{actual:?} != {expected:?}"
+        );
+    }
+}
+
+pub fn to_f32(values: &[f64]) -> Vec<f32> {
+    values.iter().map(|v| *v as f32).collect()
+}
+
+pub fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Central differences of `sum(forward(p) * diff_dst)` with respect to the parameters
+/// in `field` of `p`.
+pub fn numerical_gradient<P: Clone>(
+    p: &P,
+    field: fn(&mut P) -> &mut Vec<f64>,
+    forward: fn(&P) -> Vec<f64>,
+    diff_dst: &[f64],
+) -> Vec<f64> {
+    let eps = 1e-4;
+    let loss = |p: &P| -> f64 { forward(p).iter().zip(diff_dst).map(|(d, g)| d * g).sum() };
+
+    let len = field(&mut p.clone()).len();
+
+    (0..len)
+        .map(|k| {
+            let mut plus = p.clone();
+            field(&mut plus)[k] += eps;
+            let mut minus = p.clone();
+            field(&mut minus)[k] -= eps;
+
+            (loss(&plus) - loss(&minus)) / (2.0 * eps)
+        })
+        .collect()
+}
@@ -0,0 +1,357 @@
+mod common;
+
+use {
+    common::{
+        assert_close,
+        augru::{ldigo_to_ldgoi, reference_forward, Descs, Params, C, G, N, T},
+        numerical_gradient, to_f32,
+    },
+    onednnl::{
+        engine::Engine,
+        error::DnnlError,
+        memory::{
+            buffer::AlignedBuffer,
+            descriptor::{new_plain_descriptor, DataType, MemoryDescriptor},
+            format_tag::ldgoi,
+            Memory,
+        },
+        onednnl_sys::{
+            DNNL_ARG_AUGRU_ATTENTION, DNNL_ARG_BIAS, DNNL_ARG_DIFF_AUGRU_ATTENTION,
+            DNNL_ARG_DIFF_BIAS, DNNL_ARG_DIFF_DST_LAYER, DNNL_ARG_DIFF_SRC_LAYER,
+            DNNL_ARG_DIFF_WEIGHTS_ITER, DNNL_ARG_DIFF_WEIGHTS_LAYER, DNNL_ARG_DST_LAYER,
+            DNNL_ARG_SRC_LAYER, DNNL_ARG_WEIGHTS_ITER, DNNL_ARG_WEIGHTS_LAYER, DNNL_ARG_WORKSPACE,
+        },
+        primitive::{
+            descriptor::PrimitiveDescriptor, Backward, ExecArg, Primitive, PropBackward,
+            PropForwardInference, PropForwardTraining,
+        },
+        primitives::{
+            au_gru::{BackwardAuGru, BackwardAuGruConfig, ForwardAuGru, ForwardAuGruConfig},
+            rnn::RnnDirection,
+        },
+        stream::Stream,
+    },
+};
+
+impl Descs {
+    fn forward_config(&self) -> Result<ForwardAuGruConfig, DnnlError> {
+        ForwardAuGruConfig::builder(RnnDirection::LeftToRight)
+            .with_src_layer(self.src_layer.clone_desc()?)
+            .with_attention(self.attention.clone_desc()?)
+            .with_weights_layer(self.weights_layer.clone_desc()?)
+            .with_weights_iter(self.weights_iter.clone_desc()?)
+            .with_bias(self.bias.clone_desc()?)
+            .with_dst_layer(self.dst_layer.clone_desc()?)
+            .build()
+    }
+}
+
+#[test]
+fn test_au_gru_forward_matches_reference() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    let params = Params::new(G);
+    let descs = Descs::new(G);
+
+    let new_mem = |md: &MemoryDescriptor, data: &[f32]| {
+        Memory::new_with_user_buffer(
+            engine.clone(),
+            md.clone_desc().unwrap(),
+            AlignedBuffer::new(data).unwrap(),
+        )
+        .unwrap()
+    };
+
+    let src_layer_mem = new_mem(&descs.src_layer, &to_f32(&params.src_layer));
+    let attention_mem = new_mem(&descs.attention, &to_f32(&params.attention));
+    let weights_layer_mem = new_mem(&descs.weights_layer, &to_f32(&params.weights_layer));
+    let weights_iter_mem = new_mem(&descs.weights_iter, &to_f32(&params.weights_iter));
+    let bias_mem = new_mem(&descs.bias, &to_f32(&params.bias));
+    let dst_layer_mem = new_mem(&descs.dst_layer, &[0.0; T * N * C]);
+
+    let mut prim = Primitive::<_, PropForwardInference, _>::new::<ForwardAuGru<_>>(
+        descs.forward_config().unwrap(),
+        engine.clone(),
+    )
+    .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_SRC_LAYER as i32,
+                mem: &src_layer_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_AUGRU_ATTENTION as i32,
+                mem: &attention_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_WEIGHTS_LAYER as i32,
+                mem: &weights_layer_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_WEIGHTS_ITER as i32,
+                mem: &weights_iter_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_BIAS as i32,
+                mem: &bias_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST_LAYER as i32,
+                mem: &dst_layer_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    assert_close(
+        &dst_layer_mem.to_vec().unwrap(),
+        &reference_forward(&params, false),
+        1e-5,
+    );
+}
+
+#[test]
+fn test_au_gru_backward_matches_reference() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    let params = Params::new(G);
+    let descs = Descs::new(G);
+    let diff_dst = [1.0, -0.5, 0.25, 0.75];
+
+    let new_mem = |md: &MemoryDescriptor, data: &[f32]| {
+        Memory::new_with_user_buffer(
+            engine.clone(),
+            md.clone_desc().unwrap(),
+            AlignedBuffer::new(data).unwrap(),
+        )
+        .unwrap()
+    };
+
+    // ---------------------------------------------------
+    // 1. Forward training, which fills the workspace the backward pass reads.
+    let fwd_pd = PrimitiveDescriptor::<_, PropForwardTraining, _>::new::<ForwardAuGru<_>>(
+        descs.forward_config().unwrap(),
+        engine.clone(),
+    )
+    .unwrap();
+
+    let workspace_mem = Memory::<f32>::new_with_library_buffer(
+        engine.clone(),
+        fwd_pd.workspace_desc().unwrap().unwrap(),
+    )
+    .unwrap();
+
+    let src_layer_mem = new_mem(&descs.src_layer, &to_f32(&params.src_layer));
+    let attention_mem = new_mem(&descs.attention, &to_f32(&params.attention));
+    let weights_layer_mem = new_mem(&descs.weights_layer, &to_f32(&params.weights_layer));
+    let weights_iter_mem = new_mem(&descs.weights_iter, &to_f32(&params.weights_iter));
+    let bias_mem = new_mem(&descs.bias, &to_f32(&params.bias));
+    let dst_layer_mem = new_mem(&descs.dst_layer, &[0.0; T * N * C]);
+
+    let mut fwd_prim = Primitive::from_descriptor(fwd_pd, engine.clone()).unwrap();
+
+    let fwd_desc = fwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC_LAYER as i32,
+                    mem: &src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_AUGRU_ATTENTION as i32,
+                    mem: &attention_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_LAYER as i32,
+                    mem: &weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_ITER as i32,
+                    mem: &weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_BIAS as i32,
+                    mem: &bias_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST_LAYER as i32,
+                    mem: &dst_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WORKSPACE as i32,
+                    mem: &workspace_mem,
+                },
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    stream.wait().unwrap();
+
+    // ---------------------------------------------------
+    // 2. Backward, which reads the weights in ldgoi. Everything else defaults
+    //    to the forward descriptors.
+    let weights_ldgoi_md =
+        MemoryDescriptor::new::<5, ldgoi>([1, 1, C as i64, G as i64, C as i64], DataType::F32)
+            .unwrap();
+
+    let bwd_config = BackwardAuGruConfig::builder(&fwd_desc)
+        .with_weights_layer(weights_ldgoi_md.clone_desc().unwrap())
+        .with_weights_iter(weights_ldgoi_md.clone_desc().unwrap())
+        .build()
+        .unwrap();
+
+    let bwd_weights_layer_mem = new_mem(
+        &weights_ldgoi_md,
+        &ldigo_to_ldgoi(&to_f32(&params.weights_layer), C),
+    );
+    let bwd_weights_iter_mem = new_mem(
+        &weights_ldgoi_md,
+        &ldigo_to_ldgoi(&to_f32(&params.weights_iter), C),
+    );
+    let diff_dst_layer_mem = new_mem(&descs.dst_layer, &to_f32(&diff_dst));
+    let diff_src_layer_mem = new_mem(&descs.src_layer, &[0.0; T * N * C]);
+    let diff_attention_mem = new_mem(&descs.attention, &[0.0; T * N]);
+    let diff_weights_layer_mem = new_mem(&descs.weights_layer, &[0.0; C * G * C]);
+    let diff_weights_iter_mem = new_mem(&descs.weights_iter, &[0.0; C * G * C]);
+    let diff_bias_mem = new_mem(&descs.bias, &[0.0; G * C]);
+
+    let mut bwd_prim =
+        Primitive::<Backward, PropBackward, _>::new::<BackwardAuGru<_>>(bwd_config, engine.clone())
+            .unwrap();
+
+    bwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC_LAYER as i32,
+                    mem: &src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_AUGRU_ATTENTION as i32,
+                    mem: &attention_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_LAYER as i32,
+                    mem: &bwd_weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_ITER as i32,
+                    mem: &bwd_weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_BIAS as i32,
+                    mem: &bias_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST_LAYER as i32,
+                    mem: &dst_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST_LAYER as i32,
+                    mem: &diff_dst_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WORKSPACE as i32,
+                    mem: &workspace_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SRC_LAYER as i32,
+                    mem: &diff_src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_AUGRU_ATTENTION as i32,
+                    mem: &diff_attention_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_WEIGHTS_LAYER as i32,
+                    mem: &diff_weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_WEIGHTS_ITER as i32,
+                    mem: &diff_weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_BIAS as i32,
+                    mem: &diff_bias_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    // ---------------------------------------------------
+    // 3. Every gradient agrees with the reference differentiated numerically.
+    let forward: fn(&Params) -> Vec<f64> = |p| reference_forward(p, false);
+    let tolerance = 1e-4;
+
+    assert_close(
+        &diff_src_layer_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.src_layer, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_attention_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.attention, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_weights_layer_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.weights_layer, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_weights_iter_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.weights_iter, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_bias_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.bias, forward, &diff_dst),
+        tolerance,
+    );
+}
+
+#[test]
+fn test_au_gru_builder_checks_shapes() {
+    let descs = Descs::new(G);
+
+    // Weights for three hidden channels don't fit a two-channel destination.
+    let config = ForwardAuGruConfig::builder(RnnDirection::LeftToRight)
+        .with_src_layer(descs.src_layer.clone_desc().unwrap())
+        .with_attention(descs.attention.clone_desc().unwrap())
+        .with_weights_layer(new_plain_descriptor(5, vec![1, 1, 2, 3, 3], DataType::F32))
+        .with_weights_iter(new_plain_descriptor(5, vec![1, 1, 3, 3, 3], DataType::F32))
+        .with_dst_layer(descs.dst_layer.clone_desc().unwrap())
+        .build();
+
+    assert_eq!(config.err(), Some(DnnlError::InvalidShape));
+
+    // A bidirectional layer needs weights for both directions.
+    let config = ForwardAuGruConfig::builder(RnnDirection::BidirectionalSum)
+        .with_src_layer(descs.src_layer.clone_desc().unwrap())
+        .with_attention(descs.attention.clone_desc().unwrap())
+        .with_weights_layer(descs.weights_layer.clone_desc().unwrap())
+        .with_weights_iter(descs.weights_iter.clone_desc().unwrap())
+        .with_dst_layer(descs.dst_layer.clone_desc().unwrap())
+        .build();
+
+    assert_eq!(config.err(), Some(DnnlError::InvalidShape));
+
+    // The attention is required.
+    let config = ForwardAuGruConfig::builder(RnnDirection::LeftToRight)
+        .with_src_layer(descs.src_layer.clone_desc().unwrap())
+        .with_weights_layer(descs.weights_layer.clone_desc().unwrap())
+        .with_weights_iter(descs.weights_iter.clone_desc().unwrap())
+        .with_dst_layer(descs.dst_layer.clone_desc().unwrap())
+        .build();
+
+    assert_eq!(config.err(), Some(DnnlError::InvalidArguments));
+}
@@ -0,0 +1,183 @@
+mod common;
+
+use {
+    common::assert_close,
+    onednnl::{
+        engine::Engine,
+        memory::{
+            buffer::AlignedBuffer,
+            descriptor::{new_plain_descriptor, DataType, MemoryDescriptor},
+            Memory,
+        },
+        onednnl_sys::{
+            DNNL_ARG_DIFF_DST, DNNL_ARG_DIFF_SRC, DNNL_ARG_DST, DNNL_ARG_MEAN, DNNL_ARG_SRC,
+            DNNL_ARG_VARIANCE, DNNL_ARG_WORKSPACE,
+        },
+        primitive::{
+            attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor, Backward, ExecArg,
+            Primitive, PropBackwardData, PropForwardTraining,
+        },
+        primitives::{
+            batch_norm::{
+                BackwardBatchNorm, BackwardBatchNormConfig, ForwardBatchNorm,
+                ForwardBatchNormConfig,
+            },
+            NormalizationFlags,
+        },
+        stream::Stream,
+    },
+};
+
+#[test]
+fn test_batch_norm_fused_relu_forward_backward() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. A single channel spread over two images of two values each, so the
+    //    statistics cover [1, 2, 3, 4]: mean 2.5 and variance 1.25.
+    let src_md = new_plain_descriptor(4, vec![2, 1, 1, 2], DataType::F32);
+    let stat_md = new_plain_descriptor(1, vec![1], DataType::F32);
+
+    let new_mem = |md: &MemoryDescriptor, data: &[f32]| {
+        Memory::new_with_user_buffer(
+            engine.clone(),
+            md.clone_desc().unwrap(),
+            AlignedBuffer::new(data).unwrap(),
+        )
+        .unwrap()
+    };
+
+    let src_mem = new_mem(&src_md, &[1.0, 2.0, 3.0, 4.0]);
+    let dst_mem = new_mem(&src_md, &[0.0; 4]);
+    let mean_mem = new_mem(&stat_md, &[0.0]);
+    let variance_mem = new_mem(&stat_md, &[0.0]);
+
+    let fwd_config = ForwardBatchNormConfig {
+        src_desc: src_md.clone_desc().unwrap(),
+        dst_desc: src_md.clone_desc().unwrap(),
+        epsilon: 0.0,
+        flags: NormalizationFlags::FUSE_NORM_RELU,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let fwd_pd = PrimitiveDescriptor::<_, PropForwardTraining, _>::new::<ForwardBatchNorm<_>>(
+        fwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    // The fused ReLU records which outputs were clipped in the workspace.
+    let workspace_md = fwd_pd.workspace_desc().unwrap().unwrap();
+    let workspace_mem =
+        Memory::<f32>::new_with_library_buffer(engine.clone(), workspace_md).unwrap();
+
+    let mut fwd_prim = Primitive::from_descriptor(fwd_pd, engine.clone()).unwrap();
+
+    let fwd_desc = fwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC as i32,
+                    mem: &src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST as i32,
+                    mem: &dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_MEAN as i32,
+                    mem: &mean_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_VARIANCE as i32,
+                    mem: &variance_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WORKSPACE as i32,
+                    mem: &workspace_mem,
+                },
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    stream.wait().unwrap();
+
+    let sigma = 1.25f32.sqrt();
+    let centered = [-1.5f32, -0.5, 0.5, 1.5];
+    let expected: Vec<f32> = centered.iter().map(|c| (c / sigma).max(0.0)).collect();
+
+    assert_close(&mean_mem.to_vec().unwrap(), &[2.5], 1e-5);
+    assert_close(&variance_mem.to_vec().unwrap(), &[1.25], 1e-5);
+    assert_close(&dst_mem.to_vec().unwrap(), &expected, 1e-5);
+
+    // ---------------------------------------------------
+    // 2. Backward data with diff_dst of ones. The ReLU stops the gradient for
+    //    the two negative outputs before it reaches the normalization.
+    let diff_dst_mem = new_mem(&src_md, &[1.0; 4]);
+    let diff_src_mem = new_mem(&src_md, &[0.0; 4]);
+
+    let bwd_config = BackwardBatchNormConfig {
+        diff_src_desc: src_md.clone_desc().unwrap(),
+        diff_dst_desc: src_md.clone_desc().unwrap(),
+        src_desc: src_md.clone_desc().unwrap(),
+        epsilon: 0.0,
+        flags: NormalizationFlags::FUSE_NORM_RELU,
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_prim = Primitive::<Backward, PropBackwardData, _>::new::<BackwardBatchNorm<_>>(
+        bwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    bwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC as i32,
+                    mem: &src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_MEAN as i32,
+                    mem: &mean_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_VARIANCE as i32,
+                    mem: &variance_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST as i32,
+                    mem: &diff_dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WORKSPACE as i32,
+                    mem: &workspace_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SRC as i32,
+                    mem: &diff_src_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    // diff_src = (dy - mean(dy) - x_hat * mean(dy * x_hat)) / sigma, with
+    // dy = [0, 0, 1, 1] after the ReLU.
+    let dy = [0.0f32, 0.0, 1.0, 1.0];
+    let x_hat: Vec<f32> = centered.iter().map(|c| c / sigma).collect();
+    let mean_dy = dy.iter().sum::<f32>() / 4.0;
+    let mean_dy_x_hat = dy.iter().zip(&x_hat).map(|(d, x)| d * x).sum::<f32>() / 4.0;
+    let expected_diff_src: Vec<f32> = dy
+        .iter()
+        .zip(&x_hat)
+        .map(|(d, x)| (d - mean_dy - x * mean_dy_x_hat) / sigma)
+        .collect();
+
+    assert_close(&diff_src_mem.to_vec().unwrap(), &expected_diff_src, 1e-5);
+}
@@ -0,0 +1,200 @@
+use onednnl::{
+    engine::Engine,
+    error::DnnlError,
+    memory::{
+        buffer::AlignedBuffer,
+        descriptor::{new_plain_descriptor, DataType, MemoryDescriptor},
+        Memory,
+    },
+    onednnl_sys::{DNNL_ARG_DST, DNNL_ARG_SRC_0, DNNL_ARG_SRC_1, DNNL_ARG_SRC_2},
+    primitive::{
+        attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor, ExecArg, Forward,
+        Primitive, PropForwardInference,
+    },
+    primitives::binary::{
+        broadcast_src1_desc, Binary, Broadcast, ForwardBinary, ForwardBinaryConfig,
+    },
+    stream::Stream,
+};
+
+#[test]
+fn test_binary_select() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. Pick between two rows with an s8 condition.
+    let md = new_plain_descriptor(1, vec![4], DataType::F32);
+    let cond_md = new_plain_descriptor(1, vec![4], DataType::S8);
+
+    let config = ForwardBinaryConfig {
+        alg_kind: Binary::SELECT,
+        src0_desc: md.clone_desc().unwrap(),
+        src1_desc: md.clone_desc().unwrap(),
+        src2_desc: Some(cond_md.clone_desc().unwrap()),
+        dst_desc: md.clone_desc().unwrap(),
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut prim = Primitive::<Forward, PropForwardInference, _>::new::<ForwardBinary<_>>(
+        config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let src0_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[1.0f32, 2.0, 3.0, 4.0]).unwrap(),
+    )
+    .unwrap();
+    let src1_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[10.0f32, 20.0, 30.0, 40.0]).unwrap(),
+    )
+    .unwrap();
+    let cond_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        cond_md,
+        AlignedBuffer::new(&[1i8, 0, 0, 1]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem =
+        Memory::new_with_user_buffer(engine.clone(), md, AlignedBuffer::<f32>::zeroed(4).unwrap())
+            .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_SRC_0 as i32,
+                mem: &src0_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_SRC_1 as i32,
+                mem: &src1_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_SRC_2 as i32,
+                mem: &cond_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    // ---------------------------------------------------
+    // 2. src0 where the condition is set, src1 elsewhere.
+    assert_eq!(dst_mem.to_vec().unwrap(), vec![1.0, 20.0, 30.0, 4.0]);
+}
+
+#[test]
+fn test_binary_select_needs_condition() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+
+    let md = new_plain_descriptor(1, vec![4], DataType::F32);
+
+    let config = ForwardBinaryConfig {
+        alg_kind: Binary::SELECT,
+        src0_desc: md.clone_desc().unwrap(),
+        src1_desc: md.clone_desc().unwrap(),
+        src2_desc: None,
+        dst_desc: md,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let pd = PrimitiveDescriptor::<Forward, PropForwardInference, _>::new::<ForwardBinary<_>>(
+        config, engine,
+    );
+
+    assert!(matches!(pd, Err(DnnlError::InvalidArguments)));
+}
+
+#[test]
+fn test_binary_per_channel_broadcast() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // Add one bias per channel to two channels of two values each.
+    let src0_md = new_plain_descriptor(3, vec![1, 2, 2], DataType::F32);
+    let src1_md = broadcast_src1_desc(&src0_md, Broadcast::PerChannel).unwrap();
+
+    let config = ForwardBinaryConfig {
+        alg_kind: Binary::ADD,
+        src0_desc: src0_md.clone_desc().unwrap(),
+        src1_desc: src1_md.clone_desc().unwrap(),
+        src2_desc: None,
+        dst_desc: src0_md.clone_desc().unwrap(),
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut prim = Primitive::<Forward, PropForwardInference, _>::new::<ForwardBinary<_>>(
+        config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let src0_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src0_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[1.0f32, 2.0, 3.0, 4.0]).unwrap(),
+    )
+    .unwrap();
+    let src1_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src1_md,
+        AlignedBuffer::new(&[10.0f32, 20.0]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src0_md,
+        AlignedBuffer::<f32>::zeroed(4).unwrap(),
+    )
+    .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_SRC_0 as i32,
+                mem: &src0_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_SRC_1 as i32,
+                mem: &src1_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    assert_eq!(dst_mem.to_vec().unwrap(), vec![11.0, 12.0, 23.0, 24.0]);
+}
+
+#[test]
+fn test_broadcast_needs_dimensions() {
+    // A descriptor without dimensions has nothing to broadcast over.
+    let zero_md = MemoryDescriptor::new_any(&[], DataType::F32).unwrap();
+
+    assert!(matches!(
+        broadcast_src1_desc(&zero_md, Broadcast::PerTensor),
+        Err(DnnlError::InvalidShape)
+    ));
+
+    let md = new_plain_descriptor(1, vec![4], DataType::F32);
+
+    assert!(matches!(
+        broadcast_src1_desc(&md, Broadcast::PerChannel),
+        Err(DnnlError::InvalidShape)
+    ));
+}
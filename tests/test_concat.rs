@@ -0,0 +1,164 @@
+use onednnl::{
+    engine::Engine,
+    error::DnnlError,
+    memory::{
+        buffer::AlignedBuffer,
+        descriptor::{new_plain_descriptor, DataType, DimsQuery},
+        Memory,
+    },
+    onednnl_sys::{dnnl_query_t, DNNL_ARG_DST, DNNL_ARG_MULTIPLE_SRC},
+    primitive::{
+        attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor, ExecArg, Forward,
+        Primitive, PropAny,
+    },
+    primitives::concat::{ForwardConcat, ForwardConcatConfig},
+    stream::Stream,
+};
+
+#[test]
+fn test_concat_along_channels() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. Join a [2, 1] and a [2, 2] tensor into a [2, 3] one.
+    let src_descs = [
+        new_plain_descriptor(2, vec![2, 1], DataType::F32),
+        new_plain_descriptor(2, vec![2, 2], DataType::F32),
+    ];
+    let dst_desc = new_plain_descriptor(2, vec![2, 3], DataType::F32);
+
+    let config = ForwardConcatConfig {
+        src_descs: &src_descs,
+        concat_dimension: 1,
+        dst_desc: Some(dst_desc.clone_desc().unwrap()),
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut prim =
+        Primitive::<Forward, PropAny, _>::new::<ForwardConcat>(config, engine.clone()).unwrap();
+
+    let src0_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_descs[0].clone_desc().unwrap(),
+        AlignedBuffer::new(&[1.0f32, 2.0]).unwrap(),
+    )
+    .unwrap();
+    let src1_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_descs[1].clone_desc().unwrap(),
+        AlignedBuffer::new(&[3.0f32, 4.0, 5.0, 6.0]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem =
+        Memory::new_with_user_buffer(engine.clone(), dst_desc, AlignedBuffer::zeroed(6).unwrap())
+            .unwrap();
+
+    // ---------------------------------------------------
+    // 2. Each row is the row of the first source followed by the row of the second.
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_MULTIPLE_SRC as i32,
+                mem: &src0_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_MULTIPLE_SRC as i32 + 1,
+                mem: &src1_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    assert_eq!(dst_mem.to_vec(), Ok(vec![1.0, 3.0, 4.0, 2.0, 5.0, 6.0]));
+}
+
+#[test]
+fn test_concat_picks_destination() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    let src_descs = [
+        new_plain_descriptor(2, vec![1, 2], DataType::F32),
+        new_plain_descriptor(2, vec![1, 2], DataType::F32),
+    ];
+
+    let config = ForwardConcatConfig {
+        src_descs: &src_descs,
+        concat_dimension: 0,
+        dst_desc: None,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let pd =
+        PrimitiveDescriptor::<Forward, PropAny, _>::new::<ForwardConcat>(config, engine.clone())
+            .unwrap();
+
+    // Without a destination, oneDNN works out its shape from the sources.
+    let dst_desc = pd
+        .query_md(dnnl_query_t::dnnl_query_dst_md, 0)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(dst_desc.query::<DimsQuery>(), Ok(vec![2, 2]));
+
+    let src0_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_descs[0].clone_desc().unwrap(),
+        AlignedBuffer::new(&[1.0f32, 2.0]).unwrap(),
+    )
+    .unwrap();
+    let src1_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_descs[1].clone_desc().unwrap(),
+        AlignedBuffer::new(&[3.0f32, 4.0]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::<f32>::new_with_library_buffer(engine.clone(), dst_desc).unwrap();
+
+    let mut prim = Primitive::from_descriptor(pd, engine.clone()).unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_MULTIPLE_SRC as i32,
+                mem: &src0_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_MULTIPLE_SRC as i32 + 1,
+                mem: &src1_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    assert_eq!(dst_mem.to_vec(), Ok(vec![1.0, 2.0, 3.0, 4.0]));
+}
+
+#[test]
+fn test_concat_needs_sources() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+
+    let config = ForwardConcatConfig {
+        src_descs: &[],
+        concat_dimension: 0,
+        dst_desc: None,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let pd = PrimitiveDescriptor::<Forward, PropAny, _>::new::<ForwardConcat>(config, engine);
+
+    assert!(matches!(pd, Err(DnnlError::InvalidArguments)));
+}
@@ -0,0 +1,242 @@
+use onednnl::{
+    engine::Engine,
+    memory::{
+        buffer::AlignedBuffer,
+        descriptor::{new_plain_descriptor, DataType},
+        Memory,
+    },
+    onednnl_sys::{
+        DNNL_ARG_BIAS, DNNL_ARG_DIFF_BIAS, DNNL_ARG_DIFF_DST, DNNL_ARG_DIFF_SRC,
+        DNNL_ARG_DIFF_WEIGHTS, DNNL_ARG_DST, DNNL_ARG_SRC, DNNL_ARG_WEIGHTS,
+    },
+    primitive::{
+        attributes::PrimitiveAttributes, Backward, ExecArg, Primitive, PropBackwardData,
+        PropBackwardWeights, PropForwardTraining,
+    },
+    primitives::deconvolution::{
+        BackwardDataDeconvolution, BackwardDataDeconvolutionConfig, BackwardWeightsDeconvolution,
+        BackwardWeightsDeconvolutionConfig, Deconvolution, ForwardDeconvolution,
+        ForwardDeconvolutionConfig,
+    },
+    stream::Stream,
+};
+
+#[test]
+fn test_deconvolution_forward_backward() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. A single 2x2 image deconvolved with a single 2x2 kernel of ones,
+    //    stride 1 and no padding, which produces a 3x3 output.
+    //
+    //    src = [[1, 2],
+    //           [3, 4]]
+    let src_md = new_plain_descriptor(4, vec![1, 1, 2, 2], DataType::F32);
+    let weights_md = new_plain_descriptor(4, vec![1, 1, 2, 2], DataType::F32);
+    let bias_md = new_plain_descriptor(1, vec![1], DataType::F32);
+    let dst_md = new_plain_descriptor(4, vec![1, 1, 3, 3], DataType::F32);
+
+    let src_data: Vec<f32> = vec![1.0, 2.0, 3.0, 4.0];
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&src_data).unwrap(),
+    )
+    .unwrap();
+    let weights_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        weights_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[1.0f32; 4]).unwrap(),
+    )
+    .unwrap();
+    let bias_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        bias_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[0.5f32]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        dst_md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(9).unwrap(),
+    )
+    .unwrap();
+
+    // ---------------------------------------------------
+    // 2. Forward deconvolution
+    let fwd_config = ForwardDeconvolutionConfig {
+        alg_kind: Deconvolution::DIRECT,
+        src_desc: src_md.clone_desc().unwrap(),
+        weights_desc: weights_md.clone_desc().unwrap(),
+        bias_desc: Some(bias_md.clone_desc().unwrap()),
+        dst_desc: dst_md.clone_desc().unwrap(),
+        strides: vec![1, 1],
+        dilates: vec![0, 0],
+        padding_l: vec![0, 0],
+        padding_r: vec![0, 0],
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut fwd_prim = Primitive::<_, PropForwardTraining, _>::new::<ForwardDeconvolution<_>>(
+        fwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let fwd_desc = fwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC as i32,
+                    mem: &src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS as i32,
+                    mem: &weights_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_BIAS as i32,
+                    mem: &bias_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST as i32,
+                    mem: &dst_mem,
+                },
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    stream.wait().unwrap();
+
+    //    Every input element is scattered over the 2x2 window it produces,
+    //    overlapping contributions are summed and the bias is added.
+    assert_eq!(
+        dst_mem.to_vec().unwrap(),
+        vec![1.5, 3.5, 2.5, 4.5, 10.5, 6.5, 3.5, 7.5, 4.5]
+    );
+
+    // ---------------------------------------------------
+    // 3. Backward data with diff_dst of ones: every input element gathers
+    //    the gradient of the four outputs it contributed to.
+    let diff_dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        dst_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[1.0f32; 9]).unwrap(),
+    )
+    .unwrap();
+    let diff_src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(4).unwrap(),
+    )
+    .unwrap();
+
+    let bwd_data_config = BackwardDataDeconvolutionConfig {
+        alg_kind: Deconvolution::DIRECT,
+        diff_src_desc: src_md.clone_desc().unwrap(),
+        weights_desc: weights_md.clone_desc().unwrap(),
+        diff_dst_desc: dst_md.clone_desc().unwrap(),
+        strides: vec![1, 1],
+        dilates: vec![0, 0],
+        padding_l: vec![0, 0],
+        padding_r: vec![0, 0],
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_data_prim = Primitive::<Backward, PropBackwardData, _>::new::<
+        BackwardDataDeconvolution,
+    >(bwd_data_config, engine.clone())
+    .unwrap();
+
+    bwd_data_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST as i32,
+                    mem: &diff_dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS as i32,
+                    mem: &weights_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SRC as i32,
+                    mem: &diff_src_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    assert_eq!(diff_src_mem.to_vec().unwrap(), vec![4.0, 4.0, 4.0, 4.0]);
+
+    // ---------------------------------------------------
+    // 4. Backward weights: every kernel tap sees each input exactly once.
+    let diff_weights_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        weights_md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(4).unwrap(),
+    )
+    .unwrap();
+    let diff_bias_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        bias_md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(1).unwrap(),
+    )
+    .unwrap();
+
+    let bwd_weights_config = BackwardWeightsDeconvolutionConfig {
+        alg_kind: Deconvolution::DIRECT,
+        src_desc: src_md.clone_desc().unwrap(),
+        diff_weights_desc: weights_md.clone_desc().unwrap(),
+        diff_bias_desc: Some(bias_md.clone_desc().unwrap()),
+        diff_dst_desc: dst_md.clone_desc().unwrap(),
+        strides: vec![1, 1],
+        dilates: vec![0, 0],
+        padding_l: vec![0, 0],
+        padding_r: vec![0, 0],
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_weights_prim = Primitive::<Backward, PropBackwardWeights, _>::new::<
+        BackwardWeightsDeconvolution,
+    >(bwd_weights_config, engine.clone())
+    .unwrap();
+
+    bwd_weights_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC as i32,
+                    mem: &src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST as i32,
+                    mem: &diff_dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_WEIGHTS as i32,
+                    mem: &diff_weights_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_BIAS as i32,
+                    mem: &diff_bias_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    assert_eq!(
+        diff_weights_mem.to_vec().unwrap(),
+        vec![10.0, 10.0, 10.0, 10.0]
+    );
+    assert_eq!(diff_bias_mem.to_vec().unwrap(), vec![9.0]);
+}
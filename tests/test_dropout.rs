@@ -0,0 +1,144 @@
+use onednnl::{
+    engine::Engine,
+    memory::{
+        buffer::AlignedBuffer,
+        descriptor::{new_plain_descriptor, DataType},
+        Memory,
+    },
+    onednnl_sys::{DNNL_ARG_BIAS, DNNL_ARG_DST, DNNL_ARG_SRC, DNNL_ARG_WEIGHTS},
+    primitive::{
+        attributes::PrimitiveAttributes, ExecArg, Forward, Primitive, PropForwardTraining,
+    },
+    primitives::matmul::{ForwardMatMul, ForwardMatMulConfig},
+    stream::Stream,
+};
+
+/// Multiply `[1]` by a row of sixteen `1.5`s with dropout fused on, returning the
+/// destination and the mask.
+fn matmul_with_dropout(probability: f32, seed: i32) -> (Vec<f32>, Vec<u8>) {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    let src_md = new_plain_descriptor(2, vec![1, 1], DataType::F32);
+    let weights_md = new_plain_descriptor(2, vec![1, 16], DataType::F32);
+    let bias_md = new_plain_descriptor(2, vec![1, 16], DataType::F32);
+    let dst_md = new_plain_descriptor(2, vec![1, 16], DataType::F32);
+    let mask_md = new_plain_descriptor(2, vec![1, 16], DataType::U8);
+
+    let mut attr = PrimitiveAttributes::new().unwrap();
+    attr.set_dropout(&mask_md).unwrap();
+
+    let config = ForwardMatMulConfig {
+        src_desc: src_md.clone_desc().unwrap(),
+        weights_desc: weights_md.clone_desc().unwrap(),
+        bias_desc: bias_md.clone_desc().unwrap(),
+        dst_desc: dst_md.clone_desc().unwrap(),
+        attr,
+    };
+
+    let mut prim = Primitive::<Forward, PropForwardTraining, _>::new::<ForwardMatMul<_>>(
+        config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md,
+        AlignedBuffer::new(&[1.0f32]).unwrap(),
+    )
+    .unwrap();
+    let weights_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        weights_md,
+        AlignedBuffer::new(&[1.5f32; 16]).unwrap(),
+    )
+    .unwrap();
+    let bias_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        bias_md,
+        AlignedBuffer::<f32>::zeroed(16).unwrap(),
+    )
+    .unwrap();
+    let probability_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        new_plain_descriptor(1, vec![1], DataType::F32),
+        AlignedBuffer::new(&[probability]).unwrap(),
+    )
+    .unwrap();
+    let seed_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        new_plain_descriptor(1, vec![1], DataType::S32),
+        AlignedBuffer::new(&[seed]).unwrap(),
+    )
+    .unwrap();
+    let mask_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        mask_md,
+        AlignedBuffer::<u8>::zeroed(16).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        dst_md,
+        AlignedBuffer::<f32>::zeroed(16).unwrap(),
+    )
+    .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_SRC as i32,
+                mem: &src_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_WEIGHTS as i32,
+                mem: &weights_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_BIAS as i32,
+                mem: &bias_mem,
+            },
+            ExecArg::dropout_probability(&probability_mem),
+            ExecArg::dropout_seed(&seed_mem),
+            ExecArg::dropout_mask(&mask_mem),
+            ExecArg {
+                index: DNNL_ARG_DST as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    (dst_mem.to_vec().unwrap(), mask_mem.to_vec().unwrap())
+}
+
+#[test]
+fn test_dropout_with_zero_probability_keeps_everything() {
+    let (dst, mask) = matmul_with_dropout(0.0, 7);
+
+    assert_eq!(dst, vec![1.5; 16]);
+    assert!(mask.iter().all(|&m| m != 0));
+}
+
+#[test]
+fn test_dropout_is_reproducible_from_seed() {
+    let (dst, mask) = matmul_with_dropout(0.5, 42);
+
+    // ---------------------------------------------------
+    // 1. Kept values are scaled by 1 / (1 - 0.5), dropped ones are zero, and the mask
+    //    records which is which.
+    for (value, kept) in dst.iter().zip(mask.iter()) {
+        if *kept != 0 {
+            assert_eq!(*value, 3.0);
+        } else {
+            assert_eq!(*value, 0.0);
+        }
+    }
+
+    // ---------------------------------------------------
+    // 2. Running again with the same seed drops the same values.
+    assert_eq!(matmul_with_dropout(0.5, 42), (dst, mask));
+}
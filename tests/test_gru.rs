@@ -0,0 +1,465 @@
+mod common;
+
+use {
+    common::{assert_close, numerical_gradient, sigmoid, to_f32},
+    onednnl::{
+        engine::Engine,
+        error::DnnlError,
+        memory::{
+            buffer::AlignedBuffer,
+            descriptor::{new_plain_descriptor, DataType, MemoryDescriptor},
+            format_tag::ldgoi,
+            Memory,
+        },
+        onednnl_sys::{
+            dnnl_rnn_flags_t, DNNL_ARG_BIAS, DNNL_ARG_DIFF_BIAS, DNNL_ARG_DIFF_DST_LAYER,
+            DNNL_ARG_DIFF_SRC_LAYER, DNNL_ARG_DIFF_WEIGHTS_ITER, DNNL_ARG_DIFF_WEIGHTS_LAYER,
+            DNNL_ARG_DST_ITER, DNNL_ARG_DST_LAYER, DNNL_ARG_SRC_ITER, DNNL_ARG_SRC_LAYER,
+            DNNL_ARG_WEIGHTS_ITER, DNNL_ARG_WEIGHTS_LAYER, DNNL_ARG_WORKSPACE,
+        },
+        primitive::{
+            attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor, Backward, ExecArg,
+            Primitive, PropBackward, PropForwardInference, PropForwardTraining,
+        },
+        primitives::{
+            gru::{ForwardGru, ForwardGruConfig},
+            lbr_gru::{BackwardLbrGru, BackwardLbrGruConfig, ForwardLbrGru, ForwardLbrGruConfig},
+            rnn::RnnDirection,
+        },
+        stream::Stream,
+    },
+};
+
+// Two time steps of a batch of one through a single left-to-right layer with two
+// channels in and out.
+const T: usize = 2;
+const C: usize = 2;
+const G: usize = 3;
+
+#[derive(Clone)]
+struct Params {
+    src_layer: Vec<f64>,
+    src_iter: Vec<f64>,
+    weights_layer: Vec<f64>,
+    weights_iter: Vec<f64>,
+    bias: Vec<f64>,
+}
+
+impl Params {
+    /// Parameters for a cell with `bias_gates` rows of bias.
+    fn new(bias_gates: usize) -> Self {
+        Params {
+            src_layer: vec![0.5, -0.3, 0.2, 0.8],
+            src_iter: vec![0.0; C],
+            weights_layer: (0..C * G * C)
+                .map(|k| ((k * 7 % 11) as f64 - 5.0) / 10.0)
+                .collect(),
+            weights_iter: (0..C * G * C)
+                .map(|k| ((k * 5 % 13) as f64 - 6.0) / 10.0)
+                .collect(),
+            bias: (0..bias_gates * C)
+                .map(|k| ((k * 3 % 7) as f64 - 3.0) / 10.0)
+                .collect(),
+        }
+    }
+}
+
+/// GRU as documented by oneDNN, with weights in `ldigo` and gates ordered update,
+/// reset, output. Returns `dst_layer` and `dst_iter`.
+///
+/// Without `linear_before_reset`:
+///   o = tanh(W_o x + U_o (r * h) + b_o)
+///
+/// With it, the fourth bias row is added to the hidden product before the reset:
+///   o = tanh(W_o x + b_o + r * (U_o h + b_o'))
+fn reference_forward(p: &Params, linear_before_reset: bool) -> (Vec<f64>, Vec<f64>) {
+    let gate = |w: &[f64], input: &[f64], g: usize, o: usize| -> f64 {
+        (0..C).map(|i| input[i] * w[(i * G + g) * C + o]).sum()
+    };
+
+    let mut h = p.src_iter.clone();
+    let mut dst = Vec::with_capacity(T * C);
+
+    for t in 0..T {
+        let x = &p.src_layer[t * C..][..C];
+        let pre = |g: usize, o: usize| {
+            gate(&p.weights_layer, x, g, o) + gate(&p.weights_iter, &h, g, o) + p.bias[g * C + o]
+        };
+
+        let u: Vec<f64> = (0..C).map(|o| sigmoid(pre(0, o))).collect();
+        let r: Vec<f64> = (0..C).map(|o| sigmoid(pre(1, o))).collect();
+        let r_h: Vec<f64> = (0..C).map(|o| r[o] * h[o]).collect();
+
+        let next: Vec<f64> = (0..C)
+            .map(|o| {
+                let candidate = if linear_before_reset {
+                    gate(&p.weights_layer, x, 2, o)
+                        + p.bias[2 * C + o]
+                        + r[o] * (gate(&p.weights_iter, &h, 2, o) + p.bias[3 * C + o])
+                } else {
+                    gate(&p.weights_layer, x, 2, o)
+                        + gate(&p.weights_iter, &r_h, 2, o)
+                        + p.bias[2 * C + o]
+                }
+                .tanh();
+
+                u[o] * h[o] + (1.0 - u[o]) * candidate
+            })
+            .collect();
+
+        dst.extend_from_slice(&next);
+        h = next;
+    }
+
+    (dst, h)
+}
+
+#[test]
+fn test_gru_forward_matches_reference() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    let mut params = Params::new(G);
+    params.src_iter = vec![0.4, -0.2];
+
+    let (t, c, g) = (T as i64, C as i64, G as i64);
+
+    let src_layer_md = new_plain_descriptor(3, vec![t, 1, c], DataType::F32);
+    let iter_md = new_plain_descriptor(4, vec![1, 1, 1, c], DataType::F32);
+    let weights_md = new_plain_descriptor(5, vec![1, 1, c, g, c], DataType::F32);
+    let bias_md = new_plain_descriptor(4, vec![1, 1, g, c], DataType::F32);
+    let dst_layer_md = new_plain_descriptor(3, vec![t, 1, c], DataType::F32);
+
+    let config = ForwardGruConfig {
+        direction: RnnDirection::LeftToRight,
+        src_layer_desc: src_layer_md.clone_desc().unwrap(),
+        src_iter_desc: Some(iter_md.clone_desc().unwrap()),
+        weights_layer_desc: weights_md.clone_desc().unwrap(),
+        weights_iter_desc: weights_md.clone_desc().unwrap(),
+        bias_desc: Some(bias_md.clone_desc().unwrap()),
+        dst_layer_desc: dst_layer_md.clone_desc().unwrap(),
+        dst_iter_desc: Some(iter_md.clone_desc().unwrap()),
+        flags: dnnl_rnn_flags_t::dnnl_rnn_flags_undef,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let new_mem = |md: &MemoryDescriptor, data: &[f32]| {
+        Memory::new_with_user_buffer(
+            engine.clone(),
+            md.clone_desc().unwrap(),
+            AlignedBuffer::new(data).unwrap(),
+        )
+        .unwrap()
+    };
+
+    let src_layer_mem = new_mem(&src_layer_md, &to_f32(&params.src_layer));
+    let src_iter_mem = new_mem(&iter_md, &to_f32(&params.src_iter));
+    let weights_layer_mem = new_mem(&weights_md, &to_f32(&params.weights_layer));
+    let weights_iter_mem = new_mem(&weights_md, &to_f32(&params.weights_iter));
+    let bias_mem = new_mem(&bias_md, &to_f32(&params.bias));
+    let dst_layer_mem = new_mem(&dst_layer_md, &[0.0; T * C]);
+    let dst_iter_mem = new_mem(&iter_md, &[0.0; C]);
+
+    let mut prim =
+        Primitive::<_, PropForwardInference, _>::new::<ForwardGru<_>>(config, engine.clone())
+            .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_SRC_LAYER as i32,
+                mem: &src_layer_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_SRC_ITER as i32,
+                mem: &src_iter_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_WEIGHTS_LAYER as i32,
+                mem: &weights_layer_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_WEIGHTS_ITER as i32,
+                mem: &weights_iter_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_BIAS as i32,
+                mem: &bias_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST_LAYER as i32,
+                mem: &dst_layer_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST_ITER as i32,
+                mem: &dst_iter_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    let (dst_layer, dst_iter) = reference_forward(&params, false);
+
+    assert_close(&dst_layer_mem.to_vec().unwrap(), &dst_layer, 1e-5);
+    assert_close(&dst_iter_mem.to_vec().unwrap(), &dst_iter, 1e-5);
+}
+
+#[test]
+fn test_lbr_gru_backward_matches_reference() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    let params = Params::new(G + 1);
+    let diff_dst = [1.0, -0.5, 0.25, 0.75];
+
+    let (t, c, g) = (T as i64, C as i64, G as i64);
+
+    let src_layer_md = new_plain_descriptor(3, vec![t, 1, c], DataType::F32);
+    let weights_md = new_plain_descriptor(5, vec![1, 1, c, g, c], DataType::F32);
+    let bias_md = new_plain_descriptor(4, vec![1, 1, g + 1, c], DataType::F32);
+    let dst_layer_md = new_plain_descriptor(3, vec![t, 1, c], DataType::F32);
+
+    let new_mem = |md: &MemoryDescriptor, data: &[f32]| {
+        Memory::new_with_user_buffer(
+            engine.clone(),
+            md.clone_desc().unwrap(),
+            AlignedBuffer::new(data).unwrap(),
+        )
+        .unwrap()
+    };
+
+    // ---------------------------------------------------
+    // 1. Forward training, which fills the workspace the backward pass reads.
+    let fwd_config = ForwardLbrGruConfig {
+        direction: RnnDirection::LeftToRight,
+        src_layer_desc: src_layer_md.clone_desc().unwrap(),
+        src_iter_desc: None,
+        weights_layer_desc: weights_md.clone_desc().unwrap(),
+        weights_iter_desc: weights_md.clone_desc().unwrap(),
+        bias_desc: Some(bias_md.clone_desc().unwrap()),
+        dst_layer_desc: dst_layer_md.clone_desc().unwrap(),
+        dst_iter_desc: None,
+        flags: dnnl_rnn_flags_t::dnnl_rnn_flags_undef,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let fwd_pd = PrimitiveDescriptor::<_, PropForwardTraining, _>::new::<ForwardLbrGru<_>>(
+        fwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let workspace_mem = Memory::<f32>::new_with_library_buffer(
+        engine.clone(),
+        fwd_pd.workspace_desc().unwrap().unwrap(),
+    )
+    .unwrap();
+
+    let src_layer_mem = new_mem(&src_layer_md, &to_f32(&params.src_layer));
+    let weights_layer_mem = new_mem(&weights_md, &to_f32(&params.weights_layer));
+    let weights_iter_mem = new_mem(&weights_md, &to_f32(&params.weights_iter));
+    let bias_mem = new_mem(&bias_md, &to_f32(&params.bias));
+    let dst_layer_mem = new_mem(&dst_layer_md, &[0.0; T * C]);
+
+    let mut fwd_prim = Primitive::from_descriptor(fwd_pd, engine.clone()).unwrap();
+
+    let fwd_desc = fwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC_LAYER as i32,
+                    mem: &src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_LAYER as i32,
+                    mem: &weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_ITER as i32,
+                    mem: &weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_BIAS as i32,
+                    mem: &bias_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST_LAYER as i32,
+                    mem: &dst_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WORKSPACE as i32,
+                    mem: &workspace_mem,
+                },
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    stream.wait().unwrap();
+
+    assert_close(
+        &dst_layer_mem.to_vec().unwrap(),
+        &reference_forward(&params, true).0,
+        1e-5,
+    );
+
+    // ---------------------------------------------------
+    // 2. Backward, which reads the weights in ldgoi.
+    let weights_ldgoi_md =
+        MemoryDescriptor::new::<5, ldgoi>([1, 1, c, g, c], DataType::F32).unwrap();
+    let to_ldgoi = |weights: &[f64]| -> Vec<f32> {
+        let mut out = vec![0.0; weights.len()];
+        for i in 0..C {
+            for g in 0..G {
+                for o in 0..C {
+                    out[(g * C + o) * C + i] = weights[(i * G + g) * C + o] as f32;
+                }
+            }
+        }
+        out
+    };
+
+    let bwd_weights_layer_mem = new_mem(&weights_ldgoi_md, &to_ldgoi(&params.weights_layer));
+    let bwd_weights_iter_mem = new_mem(&weights_ldgoi_md, &to_ldgoi(&params.weights_iter));
+    let diff_dst_layer_mem = new_mem(&dst_layer_md, &to_f32(&diff_dst));
+    let diff_src_layer_mem = new_mem(&src_layer_md, &[0.0; T * C]);
+    let diff_weights_layer_mem = new_mem(&weights_md, &[0.0; C * G * C]);
+    let diff_weights_iter_mem = new_mem(&weights_md, &[0.0; C * G * C]);
+    let diff_bias_mem = new_mem(&bias_md, &[0.0; (G + 1) * C]);
+
+    let bwd_config = BackwardLbrGruConfig {
+        direction: RnnDirection::LeftToRight,
+        src_layer_desc: src_layer_md.clone_desc().unwrap(),
+        src_iter_desc: None,
+        weights_layer_desc: weights_ldgoi_md.clone_desc().unwrap(),
+        weights_iter_desc: weights_ldgoi_md.clone_desc().unwrap(),
+        bias_desc: Some(bias_md.clone_desc().unwrap()),
+        dst_layer_desc: dst_layer_md.clone_desc().unwrap(),
+        dst_iter_desc: None,
+        diff_src_layer_desc: src_layer_md.clone_desc().unwrap(),
+        diff_src_iter_desc: None,
+        diff_weights_layer_desc: weights_md.clone_desc().unwrap(),
+        diff_weights_iter_desc: weights_md.clone_desc().unwrap(),
+        diff_bias_desc: Some(bias_md.clone_desc().unwrap()),
+        diff_dst_layer_desc: dst_layer_md.clone_desc().unwrap(),
+        diff_dst_iter_desc: None,
+        flags: dnnl_rnn_flags_t::dnnl_rnn_flags_undef,
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_prim = Primitive::<Backward, PropBackward, _>::new::<BackwardLbrGru<_>>(
+        bwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    bwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC_LAYER as i32,
+                    mem: &src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_LAYER as i32,
+                    mem: &bwd_weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_ITER as i32,
+                    mem: &bwd_weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_BIAS as i32,
+                    mem: &bias_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST_LAYER as i32,
+                    mem: &dst_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST_LAYER as i32,
+                    mem: &diff_dst_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WORKSPACE as i32,
+                    mem: &workspace_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SRC_LAYER as i32,
+                    mem: &diff_src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_WEIGHTS_LAYER as i32,
+                    mem: &diff_weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_WEIGHTS_ITER as i32,
+                    mem: &diff_weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_BIAS as i32,
+                    mem: &diff_bias_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    // ---------------------------------------------------
+    // 3. Compare with central differences of sum(dst_layer * diff_dst).
+    let forward: fn(&Params) -> Vec<f64> = |p| reference_forward(p, true).0;
+
+    let tolerance = 1e-4;
+
+    assert_close(
+        &diff_src_layer_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.src_layer, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_weights_layer_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.weights_layer, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_weights_iter_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.weights_iter, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_bias_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.bias, forward, &diff_dst),
+        tolerance,
+    );
+}
+
+#[test]
+fn test_lbr_gru_rejects_three_row_bias() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+
+    let (t, c, g) = (T as i64, C as i64, G as i64);
+
+    // A bias laid out for a plain GRU is one row short for the linear-before-reset
+    // variant.
+    let config = ForwardLbrGruConfig {
+        direction: RnnDirection::LeftToRight,
+        src_layer_desc: new_plain_descriptor(3, vec![t, 1, c], DataType::F32),
+        src_iter_desc: None,
+        weights_layer_desc: new_plain_descriptor(5, vec![1, 1, c, g, c], DataType::F32),
+        weights_iter_desc: new_plain_descriptor(5, vec![1, 1, c, g, c], DataType::F32),
+        bias_desc: Some(new_plain_descriptor(4, vec![1, 1, g, c], DataType::F32)),
+        dst_layer_desc: new_plain_descriptor(3, vec![t, 1, c], DataType::F32),
+        dst_iter_desc: None,
+        flags: dnnl_rnn_flags_t::dnnl_rnn_flags_undef,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let result =
+        PrimitiveDescriptor::<_, PropForwardInference, _>::new::<ForwardLbrGru<_>>(config, engine);
+
+    assert!(matches!(result, Err(DnnlError::InvalidShape)));
+}
@@ -154,7 +154,7 @@ fn test_inner_product_nchw_to_nc_backprop() {
             .unwrap();
 
     // We'll store diff_weights in a new user buffer
-    let diff_weights_buf = AlignedBuffer::zeroed(weights_len).unwrap();
+    let diff_weights_buf = AlignedBuffer::<f32>::zeroed(weights_len).unwrap();
     let diff_weights_mem = Memory::new_with_user_buffer(
         engine.clone(),
         weights_md.clone_desc().unwrap(),
@@ -163,7 +163,7 @@ fn test_inner_product_nchw_to_nc_backprop() {
     .unwrap();
 
     // We'll store diff_bias in a new user buffer
-    let diff_bias_buf = AlignedBuffer::zeroed(bias_len).unwrap();
+    let diff_bias_buf = AlignedBuffer::<f32>::zeroed(bias_len).unwrap();
     let diff_bias_mem =
         Memory::new_with_user_buffer(engine.clone(), bias_md.clone_desc().unwrap(), diff_bias_buf)
             .unwrap();
@@ -228,7 +228,7 @@ fn test_inner_product_nchw_to_nc_backprop() {
     //    We'll produce diff_src from:
     //       - diff_dst + the original weights.
     //    The shape is the same as src_dims: [N, IC, IH, IW].
-    let diff_src_buf = AlignedBuffer::zeroed(src_len).unwrap();
+    let diff_src_buf = AlignedBuffer::<f32>::zeroed(src_len).unwrap();
     let diff_src_mem =
         Memory::new_with_user_buffer(engine.clone(), src_md.clone_desc().unwrap(), diff_src_buf)
             .unwrap();
@@ -0,0 +1,196 @@
+mod common;
+
+use {
+    common::assert_close,
+    onednnl::{
+        engine::Engine,
+        memory::{
+            buffer::AlignedBuffer,
+            descriptor::{new_plain_descriptor, DataType, MemoryDescriptor},
+            Memory,
+        },
+        onednnl_sys::{
+            DNNL_ARG_DIFF_DST, DNNL_ARG_DIFF_SCALE, DNNL_ARG_DIFF_SHIFT, DNNL_ARG_DIFF_SRC,
+            DNNL_ARG_DST, DNNL_ARG_MEAN, DNNL_ARG_SCALE, DNNL_ARG_SHIFT, DNNL_ARG_SRC,
+            DNNL_ARG_VARIANCE,
+        },
+        primitive::{
+            attributes::PrimitiveAttributes, Backward, ExecArg, Primitive, PropBackward,
+            PropForwardTraining,
+        },
+        primitives::{
+            layer_norm::{
+                BackwardLayerNorm, BackwardLayerNormConfig, ForwardLayerNorm,
+                ForwardLayerNormConfig,
+            },
+            NormalizationFlags,
+        },
+        stream::Stream,
+    },
+};
+
+#[test]
+fn test_layer_norm_forward_backward() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. Two rows of four normalized over the last dimension. The second row
+    //    is the first doubled, so both normalize to the same values.
+    let src_md = new_plain_descriptor(2, vec![2, 4], DataType::F32);
+    let stat_md = new_plain_descriptor(1, vec![2], DataType::F32);
+    let scale_shift_md = new_plain_descriptor(1, vec![4], DataType::F32);
+
+    let flags = NormalizationFlags::USE_SCALE | NormalizationFlags::USE_SHIFT;
+
+    let new_mem = |md: &MemoryDescriptor, data: &[f32]| {
+        Memory::new_with_user_buffer(
+            engine.clone(),
+            md.clone_desc().unwrap(),
+            AlignedBuffer::new(data).unwrap(),
+        )
+        .unwrap()
+    };
+
+    let src_mem = new_mem(&src_md, &[1.0, 2.0, 3.0, 4.0, 2.0, 4.0, 6.0, 8.0]);
+    let dst_mem = new_mem(&src_md, &[0.0; 8]);
+    let mean_mem = new_mem(&stat_md, &[0.0; 2]);
+    let variance_mem = new_mem(&stat_md, &[0.0; 2]);
+    let scale_mem = new_mem(&scale_shift_md, &[2.0; 4]);
+    let shift_mem = new_mem(&scale_shift_md, &[0.5; 4]);
+
+    let fwd_config = ForwardLayerNormConfig {
+        src_desc: src_md.clone_desc().unwrap(),
+        dst_desc: src_md.clone_desc().unwrap(),
+        stat_desc: Some(stat_md.clone_desc().unwrap()),
+        epsilon: 0.0,
+        flags,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut fwd_prim = Primitive::<_, PropForwardTraining, _>::new::<ForwardLayerNorm<_>>(
+        fwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let fwd_desc = fwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC as i32,
+                    mem: &src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST as i32,
+                    mem: &dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_MEAN as i32,
+                    mem: &mean_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_VARIANCE as i32,
+                    mem: &variance_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_SCALE as i32,
+                    mem: &scale_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_SHIFT as i32,
+                    mem: &shift_mem,
+                },
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    stream.wait().unwrap();
+
+    let x_hat: Vec<f32> = [-1.5f32, -0.5, 0.5, 1.5]
+        .iter()
+        .map(|v| v / 1.25f32.sqrt())
+        .collect();
+    let expected: Vec<f32> = x_hat.iter().chain(&x_hat).map(|v| 2.0 * v + 0.5).collect();
+
+    assert_close(&mean_mem.to_vec().unwrap(), &[2.5, 5.0], 1e-5);
+    assert_close(&variance_mem.to_vec().unwrap(), &[1.25, 5.0], 1e-5);
+    assert_close(&dst_mem.to_vec().unwrap(), &expected, 1e-5);
+
+    // ---------------------------------------------------
+    // 2. Backward with diff_dst of ones. The gradient is constant across each
+    //    row, so it cancels out of diff_src entirely.
+    let diff_dst_mem = new_mem(&src_md, &[1.0; 8]);
+    let diff_src_mem = new_mem(&src_md, &[0.0; 8]);
+    let diff_scale_mem = new_mem(&scale_shift_md, &[0.0; 4]);
+    let diff_shift_mem = new_mem(&scale_shift_md, &[0.0; 4]);
+
+    let bwd_config = BackwardLayerNormConfig {
+        diff_src_desc: src_md.clone_desc().unwrap(),
+        diff_dst_desc: src_md.clone_desc().unwrap(),
+        src_desc: src_md.clone_desc().unwrap(),
+        stat_desc: Some(stat_md.clone_desc().unwrap()),
+        epsilon: 0.0,
+        flags,
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_prim = Primitive::<Backward, PropBackward, _>::new::<BackwardLayerNorm<_>>(
+        bwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    bwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC as i32,
+                    mem: &src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_MEAN as i32,
+                    mem: &mean_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_VARIANCE as i32,
+                    mem: &variance_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_SCALE as i32,
+                    mem: &scale_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST as i32,
+                    mem: &diff_dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SRC as i32,
+                    mem: &diff_src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SCALE as i32,
+                    mem: &diff_scale_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SHIFT as i32,
+                    mem: &diff_shift_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    let expected_diff_scale: Vec<f32> = x_hat.iter().map(|v| 2.0 * v).collect();
+
+    assert_close(&diff_src_mem.to_vec().unwrap(), &[0.0; 8], 1e-5);
+    assert_close(
+        &diff_scale_mem.to_vec().unwrap(),
+        &expected_diff_scale,
+        1e-5,
+    );
+    assert_close(&diff_shift_mem.to_vec().unwrap(), &[2.0; 4], 1e-5);
+}
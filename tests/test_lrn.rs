@@ -0,0 +1,256 @@
+mod common;
+
+use {
+    common::assert_close,
+    onednnl::{
+        engine::Engine,
+        memory::{
+            buffer::AlignedBuffer,
+            descriptor::{new_plain_descriptor, DataType},
+            Memory,
+        },
+        onednnl_sys::{
+            DNNL_ARG_DIFF_DST, DNNL_ARG_DIFF_SRC, DNNL_ARG_DST, DNNL_ARG_SRC, DNNL_ARG_WORKSPACE,
+        },
+        primitive::{
+            attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor, Backward, ExecArg,
+            Forward, Primitive, PropBackwardData, PropForwardInference, PropForwardTraining,
+        },
+        primitives::lrn::{BackwardLrn, BackwardLrnConfig, ForwardLrn, ForwardLrnConfig, Lrn},
+        stream::Stream,
+    },
+};
+
+const LOCAL_SIZE: usize = 3;
+const ALPHA: f32 = 0.3;
+const BETA: f32 = 0.75;
+const K: f32 = 2.0;
+
+/// The window of `LOCAL_SIZE` values centered on `c`, clipped to the tensor.
+fn window(c: usize, len: usize) -> std::ops::RangeInclusive<usize> {
+    let half = (LOCAL_SIZE - 1) / 2;
+    c.saturating_sub(half)..=(c + half).min(len - 1)
+}
+
+/// `k + alpha / n * sum` for every value of `src`.
+fn denominators(src: &[f32]) -> Vec<f32> {
+    (0..src.len())
+        .map(|c| {
+            let sum: f32 = window(c, src.len()).map(|j| src[j] * src[j]).sum();
+            K + ALPHA / LOCAL_SIZE as f32 * sum
+        })
+        .collect()
+}
+
+#[test]
+fn test_lrn_across_channels_forward_backward() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. Five channels of a single pixel, normalized over windows of three channels.
+    let md = new_plain_descriptor(4, vec![1, 5, 1, 1], DataType::F32);
+
+    let src = [1.0f32, -2.0, 0.5, 3.0, -1.5];
+    let diff_dst = [0.1f32, 0.2, -0.3, 0.4, 0.5];
+
+    let fwd_config = ForwardLrnConfig {
+        alg_kind: Lrn::ACROSS_CHANNELS,
+        src_desc: md.clone_desc().unwrap(),
+        dst_desc: md.clone_desc().unwrap(),
+        local_size: LOCAL_SIZE as i64,
+        alpha: ALPHA,
+        beta: BETA,
+        k: K,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let fwd_pd = PrimitiveDescriptor::<_, PropForwardTraining, _>::new::<ForwardLrn<_>>(
+        fwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    // Whether a workspace is needed depends on the implementation oneDNN picks.
+    let workspace_mem = fwd_pd
+        .workspace_desc()
+        .unwrap()
+        .map(|desc| Memory::<f32>::new_with_library_buffer(engine.clone(), desc).unwrap());
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::new(&src).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(5).unwrap(),
+    )
+    .unwrap();
+
+    let mut fwd_prim = Primitive::from_descriptor(fwd_pd, engine.clone()).unwrap();
+
+    let mut fwd_args = vec![
+        ExecArg {
+            index: DNNL_ARG_SRC as i32,
+            mem: &src_mem,
+        },
+        ExecArg {
+            index: DNNL_ARG_DST as i32,
+            mem: &dst_mem,
+        },
+    ];
+    if let Some(workspace_mem) = &workspace_mem {
+        fwd_args.push(ExecArg {
+            index: DNNL_ARG_WORKSPACE as i32,
+            mem: workspace_mem,
+        });
+    }
+
+    let fwd_desc = fwd_prim.execute(&stream, fwd_args).unwrap().unwrap();
+    stream.wait().unwrap();
+
+    let d = denominators(&src);
+    let expected: Vec<f32> = src.iter().zip(&d).map(|(x, d)| x * d.powf(-BETA)).collect();
+
+    assert_close(&dst_mem.to_vec().unwrap(), &expected, 1e-5);
+
+    // ---------------------------------------------------
+    // 2. Backward. Each value reaches its own output directly and the outputs of its
+    //    window through their denominators.
+    let diff_dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::new(&diff_dst).unwrap(),
+    )
+    .unwrap();
+    let diff_src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(5).unwrap(),
+    )
+    .unwrap();
+
+    let bwd_config = BackwardLrnConfig {
+        alg_kind: Lrn::ACROSS_CHANNELS,
+        diff_src_desc: md.clone_desc().unwrap(),
+        diff_dst_desc: md.clone_desc().unwrap(),
+        src_desc: md,
+        local_size: LOCAL_SIZE as i64,
+        alpha: ALPHA,
+        beta: BETA,
+        k: K,
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_prim =
+        Primitive::<Backward, PropBackwardData, _>::new::<BackwardLrn>(bwd_config, engine).unwrap();
+
+    let mut bwd_args = vec![
+        ExecArg {
+            index: DNNL_ARG_SRC as i32,
+            mem: &src_mem,
+        },
+        ExecArg {
+            index: DNNL_ARG_DIFF_DST as i32,
+            mem: &diff_dst_mem,
+        },
+        ExecArg {
+            index: DNNL_ARG_DIFF_SRC as i32,
+            mem: &diff_src_mem,
+        },
+    ];
+    if let Some(workspace_mem) = &workspace_mem {
+        bwd_args.push(ExecArg {
+            index: DNNL_ARG_WORKSPACE as i32,
+            mem: workspace_mem,
+        });
+    }
+
+    bwd_prim.execute(&stream, bwd_args).unwrap();
+    stream.wait().unwrap();
+
+    let expected: Vec<f32> = (0..src.len())
+        .map(|i| {
+            (0..src.len())
+                .map(|c| {
+                    let direct = if c == i { d[c].powf(-BETA) } else { 0.0 };
+                    let through_window = if window(c, src.len()).contains(&i) {
+                        BETA * src[c] * d[c].powf(-BETA - 1.0) * 2.0 * ALPHA / LOCAL_SIZE as f32
+                            * src[i]
+                    } else {
+                        0.0
+                    };
+                    diff_dst[c] * (direct - through_window)
+                })
+                .sum()
+        })
+        .collect();
+
+    assert_close(&diff_src_mem.to_vec().unwrap(), &expected, 1e-5);
+}
+
+#[test]
+fn test_lrn_within_channel() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // A single row of five pixels. The 3x3 window is clipped to the one row, but alpha
+    // is still divided by all nine of its values.
+    let md = new_plain_descriptor(4, vec![1, 1, 1, 5], DataType::F32);
+
+    let src = [1.0f32, -2.0, 0.5, 3.0, -1.5];
+
+    let config = ForwardLrnConfig {
+        alg_kind: Lrn::WITHIN_CHANNEL,
+        src_desc: md.clone_desc().unwrap(),
+        dst_desc: md.clone_desc().unwrap(),
+        local_size: LOCAL_SIZE as i64,
+        alpha: ALPHA * LOCAL_SIZE as f32,
+        beta: BETA,
+        k: K,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut prim =
+        Primitive::<Forward, PropForwardInference, _>::new::<ForwardLrn<_>>(config, engine.clone())
+            .unwrap();
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::new(&src).unwrap(),
+    )
+    .unwrap();
+    let dst_mem =
+        Memory::new_with_user_buffer(engine.clone(), md, AlignedBuffer::<f32>::zeroed(5).unwrap())
+            .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_SRC as i32,
+                mem: &src_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    // Scaling alpha by three makes this the across channel result along the row.
+    let expected: Vec<f32> = src
+        .iter()
+        .zip(denominators(&src))
+        .map(|(x, d)| x * d.powf(-BETA))
+        .collect();
+
+    assert_close(&dst_mem.to_vec().unwrap(), &expected, 1e-5);
+}
@@ -0,0 +1,529 @@
+mod common;
+
+use {
+    common::{assert_close, numerical_gradient, sigmoid, to_f32},
+    onednnl::{
+        engine::Engine,
+        error::DnnlError,
+        memory::{
+            buffer::AlignedBuffer,
+            descriptor::{new_plain_descriptor, DataType, MemoryDescriptor},
+            format_tag::ldgoi,
+            Memory,
+        },
+        onednnl_sys::{
+            dnnl_rnn_flags_t, DNNL_ARG_BIAS, DNNL_ARG_DIFF_BIAS, DNNL_ARG_DIFF_DST_LAYER,
+            DNNL_ARG_DIFF_SRC_LAYER, DNNL_ARG_DIFF_WEIGHTS_ITER, DNNL_ARG_DIFF_WEIGHTS_LAYER,
+            DNNL_ARG_DST_ITER, DNNL_ARG_DST_ITER_C, DNNL_ARG_DST_LAYER, DNNL_ARG_SRC_ITER,
+            DNNL_ARG_SRC_ITER_C, DNNL_ARG_SRC_LAYER, DNNL_ARG_WEIGHTS_ITER, DNNL_ARG_WEIGHTS_LAYER,
+            DNNL_ARG_WEIGHTS_PEEPHOLE, DNNL_ARG_WEIGHTS_PROJECTION, DNNL_ARG_WORKSPACE,
+        },
+        primitive::{
+            attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor, Backward, ExecArg,
+            Primitive, PropBackward, PropForwardInference, PropForwardTraining,
+        },
+        primitives::{
+            lstm::{BackwardLstm, BackwardLstmConfig, ForwardLstm, ForwardLstmConfig},
+            rnn::RnnDirection,
+        },
+        stream::Stream,
+    },
+};
+
+// Two time steps of a batch of one through a single left-to-right layer with
+// two input and two hidden channels.
+const T: usize = 2;
+const SLC: usize = 2;
+const DHC: usize = 2;
+const G: usize = 4;
+
+#[derive(Clone)]
+struct Params {
+    src_layer: Vec<f64>,
+    src_iter: Vec<f64>,
+    src_iter_c: Vec<f64>,
+    weights_layer: Vec<f64>,
+    weights_iter: Vec<f64>,
+    weights_peephole: Vec<f64>,
+    weights_projection: Option<Vec<f64>>,
+    bias: Vec<f64>,
+}
+
+impl Params {
+    /// Parameters for an LSTM whose output has `dic` channels.
+    fn new(dic: usize) -> Self {
+        let values = |len: usize, step: usize, modulus: usize| -> Vec<f64> {
+            (0..len)
+                .map(|k| ((k * step % modulus) as f64 - (modulus / 2) as f64) / 10.0)
+                .collect()
+        };
+
+        Params {
+            src_layer: vec![0.5, -0.3, 0.2, 0.8],
+            src_iter: vec![0.0; dic],
+            src_iter_c: vec![0.0; DHC],
+            weights_layer: values(SLC * G * DHC, 7, 11),
+            weights_iter: values(dic * G * DHC, 5, 13),
+            weights_peephole: vec![0.0; 3 * DHC],
+            weights_projection: None,
+            bias: values(G * DHC, 3, 7),
+        }
+    }
+}
+
+/// LSTM as documented by oneDNN, with weights in `ldigo`, gates ordered input,
+/// forget, candidate, output and the projection in `ldio`. Returns `dst_layer`,
+/// `dst_iter` and `dst_iter_c`.
+fn reference_forward(p: &Params) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let gate = |w: &[f64], input: &[f64], g: usize, o: usize| -> f64 {
+        (0..input.len())
+            .map(|i| input[i] * w[(i * G + g) * DHC + o])
+            .sum()
+    };
+
+    let mut h = p.src_iter.clone();
+    let mut c = p.src_iter_c.clone();
+    let mut dst = vec![];
+
+    for t in 0..T {
+        let x = &p.src_layer[t * SLC..][..SLC];
+        let pre = |g: usize, o: usize| {
+            gate(&p.weights_layer, x, g, o) + gate(&p.weights_iter, &h, g, o) + p.bias[g * DHC + o]
+        };
+
+        let mut next_c = vec![0.0; DHC];
+        let mut h_full = vec![0.0; DHC];
+
+        for o in 0..DHC {
+            let input = sigmoid(pre(0, o) + p.weights_peephole[o] * c[o]);
+            let forget = sigmoid(pre(1, o) + p.weights_peephole[DHC + o] * c[o]);
+            let candidate = pre(2, o).tanh();
+            next_c[o] = forget * c[o] + input * candidate;
+            let output = sigmoid(pre(3, o) + p.weights_peephole[2 * DHC + o] * next_c[o]);
+            h_full[o] = output * next_c[o].tanh();
+        }
+
+        h = match &p.weights_projection {
+            Some(r) => {
+                let dic = r.len() / DHC;
+                (0..dic)
+                    .map(|k| (0..DHC).map(|j| h_full[j] * r[j * dic + k]).sum())
+                    .collect()
+            }
+            None => h_full,
+        };
+        c = next_c;
+        dst.extend_from_slice(&h);
+    }
+
+    (dst, h, c)
+}
+
+#[test]
+fn test_lstm_peephole_projection_forward_matches_reference() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. Project the two hidden channels down to a single output channel, and
+    //    start from non-zero states.
+    const DIC: usize = 1;
+
+    let mut params = Params::new(DIC);
+    params.src_iter = vec![0.2];
+    params.src_iter_c = vec![0.1, -0.4];
+    params.weights_peephole = vec![0.3, -0.2, 0.1, 0.4, -0.5, 0.2];
+    params.weights_projection = Some(vec![0.7, -0.6]);
+
+    let (t, slc, dhc, dic, g) = (T as i64, SLC as i64, DHC as i64, DIC as i64, G as i64);
+
+    let src_layer_md = new_plain_descriptor(3, vec![t, 1, slc], DataType::F32);
+    let iter_md = new_plain_descriptor(4, vec![1, 1, 1, dic], DataType::F32);
+    let iter_c_md = new_plain_descriptor(4, vec![1, 1, 1, dhc], DataType::F32);
+    let weights_layer_md = new_plain_descriptor(5, vec![1, 1, slc, g, dhc], DataType::F32);
+    let weights_iter_md = new_plain_descriptor(5, vec![1, 1, dic, g, dhc], DataType::F32);
+    let peephole_md = new_plain_descriptor(4, vec![1, 1, 3, dhc], DataType::F32);
+    let projection_md = new_plain_descriptor(4, vec![1, 1, dhc, dic], DataType::F32);
+    let bias_md = new_plain_descriptor(4, vec![1, 1, g, dhc], DataType::F32);
+    let dst_layer_md = new_plain_descriptor(3, vec![t, 1, dic], DataType::F32);
+
+    let config = ForwardLstmConfig {
+        direction: RnnDirection::LeftToRight,
+        src_layer_desc: src_layer_md.clone_desc().unwrap(),
+        src_iter_desc: Some(iter_md.clone_desc().unwrap()),
+        src_iter_c_desc: Some(iter_c_md.clone_desc().unwrap()),
+        weights_layer_desc: weights_layer_md.clone_desc().unwrap(),
+        weights_iter_desc: weights_iter_md.clone_desc().unwrap(),
+        weights_peephole_desc: Some(peephole_md.clone_desc().unwrap()),
+        weights_projection_desc: Some(projection_md.clone_desc().unwrap()),
+        bias_desc: Some(bias_md.clone_desc().unwrap()),
+        dst_layer_desc: dst_layer_md.clone_desc().unwrap(),
+        dst_iter_desc: Some(iter_md.clone_desc().unwrap()),
+        dst_iter_c_desc: Some(iter_c_md.clone_desc().unwrap()),
+        flags: dnnl_rnn_flags_t::dnnl_rnn_flags_undef,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let new_mem = |md: &MemoryDescriptor, data: &[f32]| {
+        Memory::new_with_user_buffer(
+            engine.clone(),
+            md.clone_desc().unwrap(),
+            AlignedBuffer::new(data).unwrap(),
+        )
+        .unwrap()
+    };
+
+    let src_layer_mem = new_mem(&src_layer_md, &to_f32(&params.src_layer));
+    let src_iter_mem = new_mem(&iter_md, &to_f32(&params.src_iter));
+    let src_iter_c_mem = new_mem(&iter_c_md, &to_f32(&params.src_iter_c));
+    let weights_layer_mem = new_mem(&weights_layer_md, &to_f32(&params.weights_layer));
+    let weights_iter_mem = new_mem(&weights_iter_md, &to_f32(&params.weights_iter));
+    let peephole_mem = new_mem(&peephole_md, &to_f32(&params.weights_peephole));
+    let projection_mem = new_mem(
+        &projection_md,
+        &to_f32(params.weights_projection.as_ref().unwrap()),
+    );
+    let bias_mem = new_mem(&bias_md, &to_f32(&params.bias));
+    let dst_layer_mem = new_mem(&dst_layer_md, &[0.0; T * DIC]);
+    let dst_iter_mem = new_mem(&iter_md, &[0.0; DIC]);
+    let dst_iter_c_mem = new_mem(&iter_c_md, &[0.0; DHC]);
+
+    // ---------------------------------------------------
+    // 2. Run it and compare all three outputs with the reference.
+    let mut prim =
+        Primitive::<_, PropForwardInference, _>::new::<ForwardLstm<_>>(config, engine.clone())
+            .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_SRC_LAYER as i32,
+                mem: &src_layer_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_SRC_ITER as i32,
+                mem: &src_iter_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_SRC_ITER_C as i32,
+                mem: &src_iter_c_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_WEIGHTS_LAYER as i32,
+                mem: &weights_layer_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_WEIGHTS_ITER as i32,
+                mem: &weights_iter_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_WEIGHTS_PEEPHOLE as i32,
+                mem: &peephole_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_WEIGHTS_PROJECTION as i32,
+                mem: &projection_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_BIAS as i32,
+                mem: &bias_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST_LAYER as i32,
+                mem: &dst_layer_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST_ITER as i32,
+                mem: &dst_iter_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST_ITER_C as i32,
+                mem: &dst_iter_c_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    let (dst_layer, dst_iter, dst_iter_c) = reference_forward(&params);
+
+    assert_close(&dst_layer_mem.to_vec().unwrap(), &dst_layer, 1e-5);
+    assert_close(&dst_iter_mem.to_vec().unwrap(), &dst_iter, 1e-5);
+    assert_close(&dst_iter_c_mem.to_vec().unwrap(), &dst_iter_c, 1e-5);
+}
+
+#[test]
+fn test_lstm_backward_matches_reference() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    let params = Params::new(DHC);
+    let diff_dst = [1.0, -0.5, 0.25, 0.75];
+
+    let (t, slc, dhc, g) = (T as i64, SLC as i64, DHC as i64, G as i64);
+
+    let src_layer_md = new_plain_descriptor(3, vec![t, 1, slc], DataType::F32);
+    let weights_layer_md = new_plain_descriptor(5, vec![1, 1, slc, g, dhc], DataType::F32);
+    let weights_iter_md = new_plain_descriptor(5, vec![1, 1, dhc, g, dhc], DataType::F32);
+    let bias_md = new_plain_descriptor(4, vec![1, 1, g, dhc], DataType::F32);
+    let dst_layer_md = new_plain_descriptor(3, vec![t, 1, dhc], DataType::F32);
+
+    let new_mem = |md: &MemoryDescriptor, data: &[f32]| {
+        Memory::new_with_user_buffer(
+            engine.clone(),
+            md.clone_desc().unwrap(),
+            AlignedBuffer::new(data).unwrap(),
+        )
+        .unwrap()
+    };
+
+    // ---------------------------------------------------
+    // 1. Forward training without states, peephole or projection.
+    let fwd_config = ForwardLstmConfig {
+        direction: RnnDirection::LeftToRight,
+        src_layer_desc: src_layer_md.clone_desc().unwrap(),
+        src_iter_desc: None,
+        src_iter_c_desc: None,
+        weights_layer_desc: weights_layer_md.clone_desc().unwrap(),
+        weights_iter_desc: weights_iter_md.clone_desc().unwrap(),
+        weights_peephole_desc: None,
+        weights_projection_desc: None,
+        bias_desc: Some(bias_md.clone_desc().unwrap()),
+        dst_layer_desc: dst_layer_md.clone_desc().unwrap(),
+        dst_iter_desc: None,
+        dst_iter_c_desc: None,
+        flags: dnnl_rnn_flags_t::dnnl_rnn_flags_undef,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let fwd_pd = PrimitiveDescriptor::<_, PropForwardTraining, _>::new::<ForwardLstm<_>>(
+        fwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let workspace_mem = Memory::<f32>::new_with_library_buffer(
+        engine.clone(),
+        fwd_pd.workspace_desc().unwrap().unwrap(),
+    )
+    .unwrap();
+
+    let src_layer_mem = new_mem(&src_layer_md, &to_f32(&params.src_layer));
+    let weights_layer_mem = new_mem(&weights_layer_md, &to_f32(&params.weights_layer));
+    let weights_iter_mem = new_mem(&weights_iter_md, &to_f32(&params.weights_iter));
+    let bias_mem = new_mem(&bias_md, &to_f32(&params.bias));
+    let dst_layer_mem = new_mem(&dst_layer_md, &[0.0; T * DHC]);
+
+    let mut fwd_prim = Primitive::from_descriptor(fwd_pd, engine.clone()).unwrap();
+
+    let fwd_desc = fwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC_LAYER as i32,
+                    mem: &src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_LAYER as i32,
+                    mem: &weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_ITER as i32,
+                    mem: &weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_BIAS as i32,
+                    mem: &bias_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST_LAYER as i32,
+                    mem: &dst_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WORKSPACE as i32,
+                    mem: &workspace_mem,
+                },
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    stream.wait().unwrap();
+
+    assert_close(
+        &dst_layer_mem.to_vec().unwrap(),
+        &reference_forward(&params).0,
+        1e-5,
+    );
+
+    // ---------------------------------------------------
+    // 2. Backward, reading the weights in ldgoi. SLC equals DHC, so both weights
+    //    share a descriptor.
+    let weights_ldgoi_md =
+        MemoryDescriptor::new::<5, ldgoi>([1, 1, slc, g, dhc], DataType::F32).unwrap();
+    let to_ldgoi = |weights: &[f64]| -> Vec<f32> {
+        let inputs = weights.len() / (G * DHC);
+        let mut out = vec![0.0; weights.len()];
+        for i in 0..inputs {
+            for g in 0..G {
+                for o in 0..DHC {
+                    out[(g * DHC + o) * inputs + i] = weights[(i * G + g) * DHC + o] as f32;
+                }
+            }
+        }
+        out
+    };
+
+    let bwd_weights_layer_mem = new_mem(&weights_ldgoi_md, &to_ldgoi(&params.weights_layer));
+    let bwd_weights_iter_mem = new_mem(&weights_ldgoi_md, &to_ldgoi(&params.weights_iter));
+    let diff_dst_layer_mem = new_mem(&dst_layer_md, &to_f32(&diff_dst));
+    let diff_src_layer_mem = new_mem(&src_layer_md, &[0.0; T * SLC]);
+    let diff_weights_layer_mem = new_mem(&weights_layer_md, &[0.0; SLC * G * DHC]);
+    let diff_weights_iter_mem = new_mem(&weights_iter_md, &[0.0; DHC * G * DHC]);
+    let diff_bias_mem = new_mem(&bias_md, &[0.0; G * DHC]);
+
+    let bwd_config = BackwardLstmConfig {
+        direction: RnnDirection::LeftToRight,
+        src_layer_desc: src_layer_md.clone_desc().unwrap(),
+        src_iter_desc: None,
+        src_iter_c_desc: None,
+        weights_layer_desc: weights_ldgoi_md.clone_desc().unwrap(),
+        weights_iter_desc: weights_ldgoi_md.clone_desc().unwrap(),
+        weights_peephole_desc: None,
+        weights_projection_desc: None,
+        bias_desc: Some(bias_md.clone_desc().unwrap()),
+        dst_layer_desc: dst_layer_md.clone_desc().unwrap(),
+        dst_iter_desc: None,
+        dst_iter_c_desc: None,
+        diff_src_layer_desc: src_layer_md.clone_desc().unwrap(),
+        diff_src_iter_desc: None,
+        diff_src_iter_c_desc: None,
+        diff_weights_layer_desc: weights_layer_md.clone_desc().unwrap(),
+        diff_weights_iter_desc: weights_iter_md.clone_desc().unwrap(),
+        diff_weights_peephole_desc: None,
+        diff_weights_projection_desc: None,
+        diff_bias_desc: Some(bias_md.clone_desc().unwrap()),
+        diff_dst_layer_desc: dst_layer_md.clone_desc().unwrap(),
+        diff_dst_iter_desc: None,
+        diff_dst_iter_c_desc: None,
+        flags: dnnl_rnn_flags_t::dnnl_rnn_flags_undef,
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_prim =
+        Primitive::<Backward, PropBackward, _>::new::<BackwardLstm<_>>(bwd_config, engine.clone())
+            .unwrap();
+
+    bwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC_LAYER as i32,
+                    mem: &src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_LAYER as i32,
+                    mem: &bwd_weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_ITER as i32,
+                    mem: &bwd_weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_BIAS as i32,
+                    mem: &bias_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST_LAYER as i32,
+                    mem: &dst_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST_LAYER as i32,
+                    mem: &diff_dst_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WORKSPACE as i32,
+                    mem: &workspace_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SRC_LAYER as i32,
+                    mem: &diff_src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_WEIGHTS_LAYER as i32,
+                    mem: &diff_weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_WEIGHTS_ITER as i32,
+                    mem: &diff_weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_BIAS as i32,
+                    mem: &diff_bias_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    // ---------------------------------------------------
+    // 3. Compare with central differences of sum(dst_layer * diff_dst).
+    let forward: fn(&Params) -> Vec<f64> = |p| reference_forward(p).0;
+
+    let tolerance = 1e-4;
+
+    assert_close(
+        &diff_src_layer_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.src_layer, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_weights_layer_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.weights_layer, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_weights_iter_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.weights_iter, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_bias_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.bias, forward, &diff_dst),
+        tolerance,
+    );
+}
+
+#[test]
+fn test_lstm_rejects_projection_of_wrong_shape() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+
+    let (t, slc, dhc, g) = (T as i64, SLC as i64, DHC as i64, G as i64);
+
+    // Projecting down to one output channel needs a [L, D, DHC, 1] projection, not a
+    // square one.
+    let config = ForwardLstmConfig {
+        direction: RnnDirection::LeftToRight,
+        src_layer_desc: new_plain_descriptor(3, vec![t, 1, slc], DataType::F32),
+        src_iter_desc: None,
+        src_iter_c_desc: None,
+        weights_layer_desc: new_plain_descriptor(5, vec![1, 1, slc, g, dhc], DataType::F32),
+        weights_iter_desc: new_plain_descriptor(5, vec![1, 1, 1, g, dhc], DataType::F32),
+        weights_peephole_desc: None,
+        weights_projection_desc: Some(new_plain_descriptor(4, vec![1, 1, dhc, dhc], DataType::F32)),
+        bias_desc: None,
+        dst_layer_desc: new_plain_descriptor(3, vec![t, 1, 1], DataType::F32),
+        dst_iter_desc: None,
+        dst_iter_c_desc: None,
+        flags: dnnl_rnn_flags_t::dnnl_rnn_flags_undef,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let result =
+        PrimitiveDescriptor::<_, PropForwardInference, _>::new::<ForwardLstm<_>>(config, engine);
+
+    assert!(matches!(result, Err(DnnlError::InvalidShape)));
+}
@@ -0,0 +1,225 @@
+use onednnl::{
+    engine::Engine,
+    memory::{
+        buffer::AlignedBuffer,
+        descriptor::{new_plain_descriptor, DataType},
+        Memory,
+    },
+    onednnl_sys::{
+        DNNL_ARG_DIFF_DST, DNNL_ARG_DIFF_SRC, DNNL_ARG_DST, DNNL_ARG_SRC, DNNL_ARG_WORKSPACE,
+    },
+    primitive::{
+        attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor, Backward, ExecArg,
+        Primitive, PropBackwardData, PropForwardInference, PropForwardTraining,
+    },
+    primitives::pooling::{
+        BackwardPooling, BackwardPoolingConfig, ForwardPooling, ForwardPoolingConfig, Pooling,
+    },
+    stream::Stream,
+};
+
+#[test]
+fn test_max_pooling_forward_backward() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. A single 4x4 image with 2x2 windows and stride 2.
+    //
+    //    src = [[ 1,  2,  3,  4],
+    //           [ 5,  6,  7,  8],
+    //           [ 9, 10, 11, 12],
+    //           [13, 14, 15, 16]]
+    let src_md = new_plain_descriptor(4, vec![1, 1, 4, 4], DataType::F32);
+    let dst_md = new_plain_descriptor(4, vec![1, 1, 2, 2], DataType::F32);
+
+    let src_data: Vec<f32> = (1..=16).map(|v| v as f32).collect();
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&src_data).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        dst_md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(4).unwrap(),
+    )
+    .unwrap();
+
+    // ---------------------------------------------------
+    // 2. Forward max pooling for training, which needs a workspace
+    let fwd_config = ForwardPoolingConfig {
+        alg_kind: Pooling::MAX,
+        src_desc: src_md.clone_desc().unwrap(),
+        dst_desc: dst_md.clone_desc().unwrap(),
+        strides: vec![2, 2],
+        kernel: vec![2, 2],
+        dilation: vec![0, 0],
+        padding_l: vec![0, 0],
+        padding_r: vec![0, 0],
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let fwd_pd = PrimitiveDescriptor::<_, PropForwardTraining, _>::new::<ForwardPooling<_>>(
+        fwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let workspace_md = fwd_pd.workspace_desc().unwrap().unwrap();
+    let workspace_mem =
+        Memory::<f32>::new_with_library_buffer(engine.clone(), workspace_md).unwrap();
+
+    let mut fwd_prim = Primitive::from_descriptor(fwd_pd, engine.clone()).unwrap();
+
+    let fwd_desc = fwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC as i32,
+                    mem: &src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST as i32,
+                    mem: &dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WORKSPACE as i32,
+                    mem: &workspace_mem,
+                },
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    stream.wait().unwrap();
+
+    assert_eq!(dst_mem.to_vec().unwrap(), vec![6.0, 8.0, 14.0, 16.0]);
+
+    // ---------------------------------------------------
+    // 3. Backward: the gradient flows only to the maximum of each window.
+    let diff_dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        dst_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[1.0f32; 4]).unwrap(),
+    )
+    .unwrap();
+    let diff_src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(16).unwrap(),
+    )
+    .unwrap();
+
+    let bwd_config = BackwardPoolingConfig {
+        alg_kind: Pooling::MAX,
+        diff_src_desc: src_md.clone_desc().unwrap(),
+        diff_dst_desc: dst_md.clone_desc().unwrap(),
+        strides: vec![2, 2],
+        kernel: vec![2, 2],
+        dilation: vec![0, 0],
+        padding_l: vec![0, 0],
+        padding_r: vec![0, 0],
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_prim =
+        Primitive::<Backward, PropBackwardData, _>::new::<BackwardPooling>(bwd_config, engine)
+            .unwrap();
+
+    bwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST as i32,
+                    mem: &diff_dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WORKSPACE as i32,
+                    mem: &workspace_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SRC as i32,
+                    mem: &diff_src_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    let mut expected = vec![0.0f32; 16];
+    for i in [5, 7, 13, 15] {
+        expected[i] = 1.0;
+    }
+    assert_eq!(diff_src_mem.to_vec().unwrap(), expected);
+}
+
+#[test]
+fn test_avg_pooling_exclude_padding() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // A row of three values padded by one on the left and two on the right, with
+    // 3-wide windows and stride 3. Excluding the padding, the windows average
+    // [1, 2] and [3].
+    let src_md = new_plain_descriptor(3, vec![1, 1, 3], DataType::F32);
+    let dst_md = new_plain_descriptor(3, vec![1, 1, 2], DataType::F32);
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[1.0f32, 2.0, 3.0]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        dst_md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(2).unwrap(),
+    )
+    .unwrap();
+
+    let config = ForwardPoolingConfig {
+        alg_kind: Pooling::AVG_EXCLUDE_PADDING,
+        src_desc: src_md,
+        dst_desc: dst_md,
+        strides: vec![3],
+        kernel: vec![3],
+        dilation: vec![0],
+        padding_l: vec![1],
+        padding_r: vec![2],
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let fwd_pd = PrimitiveDescriptor::<_, PropForwardInference, _>::new::<ForwardPooling<_>>(
+        config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    // Inference never needs a workspace.
+    assert_eq!(fwd_pd.workspace_desc(), Ok(None));
+
+    let mut prim = Primitive::from_descriptor(fwd_pd, engine).unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_SRC as i32,
+                mem: &src_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    assert_eq!(dst_mem.to_vec().unwrap(), vec![1.5, 3.0]);
+}
@@ -0,0 +1,160 @@
+use onednnl::{
+    engine::Engine,
+    memory::{
+        buffer::AlignedBuffer,
+        descriptor::{new_plain_descriptor, DataType, DimsQuery},
+        Memory,
+    },
+    onednnl_sys::{DNNL_ARG_DST, DNNL_ARG_SRC_0, DNNL_ARG_SRC_1},
+    primitive::{
+        attributes::PrimitiveAttributes,
+        post_ops::{PostOp, PostOps},
+        ExecArg, Forward, Primitive, PropForwardInference,
+    },
+    primitives::{
+        binary::{Binary, ForwardBinary, ForwardBinaryConfig},
+        eltwise::Unary,
+    },
+    stream::Stream,
+};
+
+#[test]
+fn test_post_ops_read_back() {
+    let src1_md = new_plain_descriptor(2, vec![1, 3], DataType::F32);
+
+    let mut post_ops = PostOps::new().unwrap();
+    post_ops.append_eltwise(Unary::CLIP, 0.0, 6.0).unwrap();
+    post_ops.append_sum(0.5, 0, DataType::F32).unwrap();
+    post_ops.append_binary(Binary::MUL, &src1_md).unwrap();
+    post_ops.append_prelu(1 << 1).unwrap();
+    post_ops
+        .append_dw(DataType::F32, DataType::F32, DataType::F32, 3, 1, 1)
+        .unwrap();
+
+    let mut attr = PrimitiveAttributes::new().unwrap();
+    attr.set_post_ops(&post_ops).unwrap();
+
+    // The attribute hands back its own copy of the chain.
+    let post_ops = attr.get_post_ops().unwrap();
+
+    assert_eq!(post_ops.len(), 5);
+    assert!(matches!(
+        post_ops.get(0),
+        Ok(PostOp::Eltwise {
+            alg_kind: Unary::CLIP,
+            alpha: 0.0,
+            beta: 6.0
+        })
+    ));
+    assert!(matches!(
+        post_ops.get(1),
+        Ok(PostOp::Sum {
+            scale: 0.5,
+            zero_point: 0,
+            data_type: DataType::F32
+        })
+    ));
+    match post_ops.get(2) {
+        Ok(PostOp::Binary {
+            alg_kind,
+            src1_desc,
+        }) => {
+            assert_eq!(alg_kind, Binary::MUL);
+            assert_eq!(src1_desc.query::<DimsQuery>(), Ok(vec![1, 3]));
+        }
+        other => panic!("expected a binary post-op, got {other:?}"),
+    }
+    assert!(matches!(post_ops.get(3), Ok(PostOp::Prelu { mask: 2 })));
+    assert!(matches!(
+        post_ops.get(4),
+        Ok(PostOp::Dw {
+            kernel_size: 3,
+            stride_size: 1,
+            padding_l_size: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_binary_with_fused_relu_and_scale() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. An add followed by a ReLU, then a multiply by a single runtime value.
+    let md = new_plain_descriptor(1, vec![4], DataType::F32);
+    let scale_md = new_plain_descriptor(1, vec![1], DataType::F32);
+
+    let mut post_ops = PostOps::new().unwrap();
+    post_ops.append_eltwise(Unary::RELU, 0.0, 0.0).unwrap();
+    post_ops.append_binary(Binary::MUL, &scale_md).unwrap();
+
+    let mut attr = PrimitiveAttributes::new().unwrap();
+    attr.set_post_ops(&post_ops).unwrap();
+
+    let config = ForwardBinaryConfig {
+        alg_kind: Binary::ADD,
+        src0_desc: md.clone_desc().unwrap(),
+        src1_desc: md.clone_desc().unwrap(),
+        src2_desc: None,
+        dst_desc: md.clone_desc().unwrap(),
+        attr,
+    };
+
+    let mut prim = Primitive::<Forward, PropForwardInference, _>::new::<ForwardBinary<_>>(
+        config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let src0_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[-1.0f32, 2.0, -3.0, 4.0]).unwrap(),
+    )
+    .unwrap();
+    let src1_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[-1.0f32, 1.0, 1.0, 1.0]).unwrap(),
+    )
+    .unwrap();
+    let scale_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        scale_md,
+        AlignedBuffer::new(&[2.0f32]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem =
+        Memory::new_with_user_buffer(engine.clone(), md, AlignedBuffer::<f32>::zeroed(4).unwrap())
+            .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_SRC_0 as i32,
+                mem: &src0_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_SRC_1 as i32,
+                mem: &src1_mem,
+            },
+            ExecArg {
+                index: PostOps::arg(1, DNNL_ARG_SRC_1 as i32),
+                mem: &scale_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    // ---------------------------------------------------
+    // 2. [-2, 3, -2, 5] clipped at zero and doubled.
+    assert_eq!(dst_mem.to_vec().unwrap(), vec![0.0, 6.0, 0.0, 10.0]);
+}
@@ -0,0 +1,160 @@
+use onednnl::{
+    engine::Engine,
+    memory::{
+        buffer::AlignedBuffer,
+        descriptor::{new_plain_descriptor, DataType},
+        Memory,
+    },
+    onednnl_sys::{
+        DNNL_ARG_DIFF_DST, DNNL_ARG_DIFF_SRC, DNNL_ARG_DIFF_WEIGHTS, DNNL_ARG_DST, DNNL_ARG_SRC,
+        DNNL_ARG_WEIGHTS,
+    },
+    primitive::{
+        attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor, Backward, ExecArg,
+        Primitive, PropBackward, PropForwardTraining,
+    },
+    primitives::prelu::{BackwardPrelu, BackwardPreluConfig, ForwardPrelu, ForwardPreluConfig},
+    stream::Stream,
+};
+
+#[test]
+fn test_prelu_forward_backward() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. Two channels of two values each, with one slope per channel.
+    //
+    //    src     = [[ 1, -2],
+    //               [-3,  4]]
+    //    weights = [0.5, 0.1]
+    let src_md = new_plain_descriptor(4, vec![1, 2, 1, 2], DataType::F32);
+    let weights_md = new_plain_descriptor(4, vec![1, 2, 1, 1], DataType::F32);
+
+    let fwd_config = ForwardPreluConfig {
+        src_desc: src_md.clone_desc().unwrap(),
+        weights_desc: weights_md.clone_desc().unwrap(),
+        dst_desc: src_md.clone_desc().unwrap(),
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let fwd_pd = PrimitiveDescriptor::<_, PropForwardTraining, _>::new::<ForwardPrelu<_>>(
+        fwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[1.0f32, -2.0, -3.0, 4.0]).unwrap(),
+    )
+    .unwrap();
+    let weights_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        weights_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[0.5f32, 0.1]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(4).unwrap(),
+    )
+    .unwrap();
+
+    let mut fwd_prim = Primitive::from_descriptor(fwd_pd, engine.clone()).unwrap();
+
+    let fwd_desc = fwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC as i32,
+                    mem: &src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS as i32,
+                    mem: &weights_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST as i32,
+                    mem: &dst_mem,
+                },
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    stream.wait().unwrap();
+
+    // Negative values are scaled by the slope of their channel.
+    assert_eq!(dst_mem.to_vec().unwrap(), vec![1.0, -1.0, -0.3, 4.0]);
+
+    // ---------------------------------------------------
+    // 2. Backward with a gradient of one everywhere.
+    let diff_dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[1.0f32; 4]).unwrap(),
+    )
+    .unwrap();
+    let diff_src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(4).unwrap(),
+    )
+    .unwrap();
+    let diff_weights_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        weights_md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(2).unwrap(),
+    )
+    .unwrap();
+
+    let bwd_config = BackwardPreluConfig {
+        src_desc: src_md.clone_desc().unwrap(),
+        weights_desc: weights_md.clone_desc().unwrap(),
+        diff_src_desc: src_md.clone_desc().unwrap(),
+        diff_weights_desc: weights_md,
+        diff_dst_desc: src_md,
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_prim =
+        Primitive::<Backward, PropBackward, _>::new::<BackwardPrelu>(bwd_config, engine).unwrap();
+
+    bwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC as i32,
+                    mem: &src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS as i32,
+                    mem: &weights_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST as i32,
+                    mem: &diff_dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SRC as i32,
+                    mem: &diff_src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_WEIGHTS as i32,
+                    mem: &diff_weights_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    // Negative values pass the gradient scaled by their slope, and each slope collects
+    // the negative values of its channel.
+    assert_eq!(diff_src_mem.to_vec().unwrap(), vec![1.0, 0.5, 0.1, 1.0]);
+    assert_eq!(diff_weights_mem.to_vec().unwrap(), vec![-2.0, -3.0]);
+}
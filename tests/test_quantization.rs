@@ -0,0 +1,118 @@
+use onednnl::{
+    engine::Engine,
+    memory::{
+        buffer::AlignedBuffer,
+        descriptor::{new_plain_descriptor, DataType},
+        Memory,
+    },
+    onednnl_sys::{DNNL_ARG_BIAS, DNNL_ARG_DST, DNNL_ARG_SRC, DNNL_ARG_WEIGHTS},
+    primitive::{
+        attributes::PrimitiveAttributes, ExecArg, Forward, Primitive, PropForwardInference,
+    },
+    primitives::matmul::{ForwardMatMul, ForwardMatMulConfig},
+    stream::Stream,
+};
+
+#[test]
+fn test_int8_matmul_with_weight_scales_and_src_zero_point() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. A u8 row times an s8 [2, 2] matrix into f32, with one scale per output column
+    //    and a single zero point for the source.
+    //
+    //    src     = [3, 5]    zero point 1
+    //    weights = [[1, 2],
+    //               [3, 1]]  scales [0.5, 2.0]
+    let src_md = new_plain_descriptor(2, vec![1, 2], DataType::U8);
+    let weights_md = new_plain_descriptor(2, vec![2, 2], DataType::S8);
+    let bias_md = new_plain_descriptor(2, vec![1, 2], DataType::F32);
+    let dst_md = new_plain_descriptor(2, vec![1, 2], DataType::F32);
+
+    let mut attr = PrimitiveAttributes::new().unwrap();
+    attr.set_scales_mask(DNNL_ARG_WEIGHTS as i32, 1 << 1)
+        .unwrap();
+    attr.set_zero_points_mask(DNNL_ARG_SRC as i32, 0).unwrap();
+
+    let config = ForwardMatMulConfig {
+        src_desc: src_md.clone_desc().unwrap(),
+        weights_desc: weights_md.clone_desc().unwrap(),
+        bias_desc: bias_md.clone_desc().unwrap(),
+        dst_desc: dst_md.clone_desc().unwrap(),
+        attr,
+    };
+
+    let mut prim = Primitive::<Forward, PropForwardInference, _>::new::<ForwardMatMul<_>>(
+        config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md,
+        AlignedBuffer::new(&[3u8, 5]).unwrap(),
+    )
+    .unwrap();
+    let weights_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        weights_md,
+        AlignedBuffer::new(&[1i8, 2, 3, 1]).unwrap(),
+    )
+    .unwrap();
+    let bias_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        bias_md,
+        AlignedBuffer::<f32>::zeroed(2).unwrap(),
+    )
+    .unwrap();
+    let scales_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        new_plain_descriptor(1, vec![2], DataType::F32),
+        AlignedBuffer::new(&[0.5f32, 2.0]).unwrap(),
+    )
+    .unwrap();
+    let zero_points_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        new_plain_descriptor(1, vec![1], DataType::S32),
+        AlignedBuffer::new(&[1i32]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        dst_md,
+        AlignedBuffer::<f32>::zeroed(2).unwrap(),
+    )
+    .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_SRC as i32,
+                mem: &src_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_WEIGHTS as i32,
+                mem: &weights_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_BIAS as i32,
+                mem: &bias_mem,
+            },
+            ExecArg::scales(DNNL_ARG_WEIGHTS as i32, &scales_mem),
+            ExecArg::zero_points(DNNL_ARG_SRC as i32, &zero_points_mem),
+            ExecArg {
+                index: DNNL_ARG_DST as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    // ---------------------------------------------------
+    // 2. [2, 4] after the zero point gives [14, 8], scaled to [7, 16].
+    assert_eq!(dst_mem.to_vec().unwrap(), vec![7.0, 16.0]);
+}
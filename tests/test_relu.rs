@@ -51,7 +51,7 @@ fn test_relu_forward_backward() {
     // 3c. Allocate memory for the forward result
 
     let a_buffer =
-        AlignedBuffer::zeroed(dst_md.get_size() / data_type_size(DataType::F32)).unwrap();
+        AlignedBuffer::<f32>::zeroed(dst_md.get_size() / data_type_size(DataType::F32)).unwrap();
 
     let dst_mem = Memory::new_with_user_buffer(engine.clone(), dst_md, a_buffer).unwrap();
 
@@ -133,7 +133,8 @@ fn test_relu_forward_backward() {
         Memory::new_with_user_buffer(engine.clone(), diff_dst_md, diff_dst_data).unwrap();
 
     let a_buffer =
-        AlignedBuffer::zeroed(diff_src_md.get_size() / data_type_size(DataType::F32)).unwrap();
+        AlignedBuffer::<f32>::zeroed(diff_src_md.get_size() / data_type_size(DataType::F32))
+            .unwrap();
 
     let diff_src_mem = Memory::new_with_user_buffer(engine.clone(), diff_src_md, a_buffer).unwrap();
 
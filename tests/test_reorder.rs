@@ -0,0 +1,186 @@
+use onednnl::{
+    engine::Engine,
+    memory::{
+        buffer::AlignedBuffer,
+        descriptor::{new_plain_descriptor, DataType, MemoryDescriptor},
+        format_tag::acdb,
+        Memory,
+    },
+    onednnl_sys::{DNNL_ARG_ATTR_SCALES, DNNL_ARG_DST, DNNL_ARG_FROM, DNNL_ARG_TO},
+    primitive::{attributes::PrimitiveAttributes, ExecArg, Forward, Primitive, PropAny},
+    primitives::reorder::{ForwardReorder, ForwardReorderConfig},
+    stream::Stream,
+};
+
+#[test]
+fn test_reorder_nchw_to_nhwc() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. Move the channels of a [1, 2, 2, 2] tensor to the innermost dimension.
+    let src_desc = new_plain_descriptor(4, vec![1, 2, 2, 2], DataType::F32);
+    let dst_desc = MemoryDescriptor::new::<4, acdb>([1, 2, 2, 2], DataType::F32).unwrap();
+
+    let config = ForwardReorderConfig {
+        src_desc: src_desc.clone_desc().unwrap(),
+        src_engine: Some(engine.clone()),
+        dst_desc: dst_desc.clone_desc().unwrap(),
+        dst_engine: Some(engine.clone()),
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut prim =
+        Primitive::<Forward, PropAny, _>::new::<ForwardReorder>(config, engine.clone()).unwrap();
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_desc,
+        AlignedBuffer::new(&[0.0f32, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem =
+        Memory::new_with_user_buffer(engine.clone(), dst_desc, AlignedBuffer::zeroed(8).unwrap())
+            .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_FROM as i32,
+                mem: &src_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_TO as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    // ---------------------------------------------------
+    // 2. Each spatial position now holds both of its channels next to each other.
+    assert_eq!(
+        dst_mem.to_vec(),
+        Ok(vec![0.0, 4.0, 1.0, 5.0, 2.0, 6.0, 3.0, 7.0])
+    );
+}
+
+#[test]
+fn test_reorder_f32_to_s8_with_scales() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    let src_desc = new_plain_descriptor(1, vec![4], DataType::F32);
+    let dst_desc = new_plain_descriptor(1, vec![4], DataType::S8);
+
+    // A single destination scale, dividing every value by 0.5 before it is rounded.
+    let mut attr = PrimitiveAttributes::new().unwrap();
+    attr.set_scales_mask(DNNL_ARG_DST as i32, 0).unwrap();
+
+    let config = ForwardReorderConfig {
+        src_desc: src_desc.clone_desc().unwrap(),
+        src_engine: None,
+        dst_desc: dst_desc.clone_desc().unwrap(),
+        dst_engine: None,
+        attr,
+    };
+
+    let mut prim =
+        Primitive::<Forward, PropAny, _>::new::<ForwardReorder>(config, engine.clone()).unwrap();
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_desc,
+        AlignedBuffer::new(&[1.0f32, -2.5, 100.0, 0.3]).unwrap(),
+    )
+    .unwrap();
+    let scale_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        new_plain_descriptor(1, vec![1], DataType::F32),
+        AlignedBuffer::new(&[0.5f32]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        dst_desc,
+        AlignedBuffer::<i8>::zeroed(4).unwrap(),
+    )
+    .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_FROM as i32,
+                mem: &src_mem,
+            },
+            ExecArg {
+                index: (DNNL_ARG_ATTR_SCALES | DNNL_ARG_DST) as i32,
+                mem: &scale_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_TO as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    // 200 saturates to the largest s8 value.
+    assert_eq!(dst_mem.to_vec(), Ok(vec![2, -5, 127, 1]));
+}
+
+#[test]
+fn test_reorder_f32_to_bf16() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    let src_desc = new_plain_descriptor(1, vec![3], DataType::F32);
+    let dst_desc = new_plain_descriptor(1, vec![3], DataType::BF16);
+
+    let config = ForwardReorderConfig {
+        src_desc: src_desc.clone_desc().unwrap(),
+        src_engine: None,
+        dst_desc: dst_desc.clone_desc().unwrap(),
+        dst_engine: None,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut prim =
+        Primitive::<Forward, PropAny, _>::new::<ForwardReorder>(config, engine.clone()).unwrap();
+
+    let src = [1.0f32, -2.0, 0.5];
+    let src_mem =
+        Memory::new_with_user_buffer(engine.clone(), src_desc, AlignedBuffer::new(&src).unwrap())
+            .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        dst_desc,
+        AlignedBuffer::<u16>::zeroed(3).unwrap(),
+    )
+    .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_FROM as i32,
+                mem: &src_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_TO as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    // These values fit in bf16, which keeps the upper half of the f32 bits.
+    let expected: Vec<u16> = src.iter().map(|v| (v.to_bits() >> 16) as u16).collect();
+
+    assert_eq!(dst_mem.to_vec(), Ok(expected));
+}
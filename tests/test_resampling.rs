@@ -0,0 +1,240 @@
+use onednnl::{
+    engine::Engine,
+    error::DnnlError,
+    memory::{
+        buffer::AlignedBuffer,
+        descriptor::{new_plain_descriptor, DataType, DimsQuery},
+        Memory,
+    },
+    onednnl_sys::{dnnl_query_t, DNNL_ARG_DIFF_DST, DNNL_ARG_DIFF_SRC, DNNL_ARG_DST, DNNL_ARG_SRC},
+    primitive::{
+        attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor, Backward, ExecArg,
+        Forward, Primitive, PropBackwardData, PropForwardInference, PropForwardTraining,
+    },
+    primitives::resampling::{
+        BackwardResampling, BackwardResamplingConfig, ForwardResampling, ForwardResamplingConfig,
+        Resampling,
+    },
+    stream::Stream,
+};
+
+#[test]
+fn test_nearest_resampling_forward_backward() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. Double the height and width of a single 2x2 image.
+    //
+    //    src = [[1, 2],
+    //           [3, 4]]
+    let src_md = new_plain_descriptor(4, vec![1, 1, 2, 2], DataType::F32);
+
+    let fwd_config = ForwardResamplingConfig {
+        alg_kind: Resampling::NEAREST,
+        factors: Some(vec![2.0, 2.0]),
+        src_desc: src_md.clone_desc().unwrap(),
+        dst_desc: None,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let fwd_pd = PrimitiveDescriptor::<_, PropForwardTraining, _>::new::<ForwardResampling<_>>(
+        fwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    // Without a destination, its shape comes from the factors.
+    let dst_md = fwd_pd
+        .query_md(dnnl_query_t::dnnl_query_dst_md, 0)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(dst_md.query::<DimsQuery>(), Ok(vec![1, 1, 4, 4]));
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[1.0f32, 2.0, 3.0, 4.0]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        dst_md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(16).unwrap(),
+    )
+    .unwrap();
+
+    let mut fwd_prim = Primitive::from_descriptor(fwd_pd, engine.clone()).unwrap();
+
+    let fwd_desc = fwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC as i32,
+                    mem: &src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST as i32,
+                    mem: &dst_mem,
+                },
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    stream.wait().unwrap();
+
+    // Every value fills a 2x2 block.
+    assert_eq!(
+        dst_mem.to_vec().unwrap(),
+        vec![
+            1.0, 1.0, 2.0, 2.0, //
+            1.0, 1.0, 2.0, 2.0, //
+            3.0, 3.0, 4.0, 4.0, //
+            3.0, 3.0, 4.0, 4.0,
+        ]
+    );
+
+    // ---------------------------------------------------
+    // 2. Backward: each source value collects the gradient of its block.
+    let diff_dst_data: Vec<f32> = (1..=16).map(|v| v as f32).collect();
+    let diff_dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        dst_md.clone_desc().unwrap(),
+        AlignedBuffer::new(&diff_dst_data).unwrap(),
+    )
+    .unwrap();
+    let diff_src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(4).unwrap(),
+    )
+    .unwrap();
+
+    let bwd_config = BackwardResamplingConfig {
+        alg_kind: Resampling::NEAREST,
+        factors: Some(vec![2.0, 2.0]),
+        diff_src_desc: src_md,
+        diff_dst_desc: dst_md,
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_prim =
+        Primitive::<Backward, PropBackwardData, _>::new::<BackwardResampling>(bwd_config, engine)
+            .unwrap();
+
+    bwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST as i32,
+                    mem: &diff_dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SRC as i32,
+                    mem: &diff_src_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    // 1 + 2 + 5 + 6, 3 + 4 + 7 + 8, 9 + 10 + 13 + 14 and 11 + 12 + 15 + 16.
+    assert_eq!(diff_src_mem.to_vec().unwrap(), vec![14.0, 22.0, 46.0, 54.0]);
+}
+
+#[test]
+fn test_linear_resampling_to_dst_shape() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // Stretch a row of two values to four. Sample positions are pixel centers, so the
+    // outer outputs land beyond the source and take the edge values.
+    let src_md = new_plain_descriptor(3, vec![1, 1, 2], DataType::F32);
+    let dst_md = new_plain_descriptor(3, vec![1, 1, 4], DataType::F32);
+
+    let config = ForwardResamplingConfig {
+        alg_kind: Resampling::LINEAR,
+        factors: None,
+        src_desc: src_md.clone_desc().unwrap(),
+        dst_desc: Some(dst_md.clone_desc().unwrap()),
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut prim = Primitive::<Forward, PropForwardInference, _>::new::<ForwardResampling<_>>(
+        config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        src_md,
+        AlignedBuffer::new(&[0.0f32, 4.0]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        dst_md,
+        AlignedBuffer::<f32>::zeroed(4).unwrap(),
+    )
+    .unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_SRC as i32,
+                mem: &src_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    assert_eq!(dst_mem.to_vec().unwrap(), vec![0.0, 1.0, 3.0, 4.0]);
+}
+
+#[test]
+fn test_resampling_needs_factors_or_dst() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+
+    let src_md = new_plain_descriptor(4, vec![1, 1, 2, 2], DataType::F32);
+
+    let config = ForwardResamplingConfig {
+        alg_kind: Resampling::NEAREST,
+        factors: None,
+        src_desc: src_md.clone_desc().unwrap(),
+        dst_desc: None,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let pd = PrimitiveDescriptor::<_, PropForwardInference, _>::new::<ForwardResampling<_>>(
+        config,
+        engine.clone(),
+    );
+
+    assert!(matches!(pd, Err(DnnlError::InvalidArguments)));
+
+    // One factor for a tensor with two spatial dimensions.
+    let config = ForwardResamplingConfig {
+        alg_kind: Resampling::NEAREST,
+        factors: Some(vec![2.0]),
+        src_desc: src_md,
+        dst_desc: None,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let pd = PrimitiveDescriptor::<_, PropForwardInference, _>::new::<ForwardResampling<_>>(
+        config, engine,
+    );
+
+    assert!(matches!(pd, Err(DnnlError::InvalidArguments)));
+}
@@ -0,0 +1,194 @@
+use {
+    onednnl::{
+        engine::Engine,
+        memory::{
+            buffer::AlignedBuffer,
+            descriptor::{new_plain_descriptor, DataType},
+            AnyMemory, Memory,
+        },
+        onednnl_sys::{
+            dnnl_rnn_flags_t, dnnl_scratchpad_mode_t, DNNL_ARG_BIAS, DNNL_ARG_DST_LAYER,
+            DNNL_ARG_SRC_LAYER, DNNL_ARG_WEIGHTS_ITER, DNNL_ARG_WEIGHTS_LAYER,
+        },
+        primitive::{
+            attributes::PrimitiveAttributes, shared_scratchpad_desc, ExecArg, Forward, Primitive,
+            PropForwardInference,
+        },
+        primitives::{
+            gru::{ForwardGru, ForwardGruConfig},
+            rnn::RnnDirection,
+        },
+        stream::Stream,
+    },
+    std::sync::Arc,
+};
+
+const G: i64 = 3;
+
+type GruPrimitive = Primitive<'static, Forward, PropForwardInference, ForwardGruConfig>;
+
+fn user_scratchpad_attr() -> PrimitiveAttributes {
+    let mut attr = PrimitiveAttributes::new().unwrap();
+    attr.set_scratchpad_mode(dnnl_scratchpad_mode_t::dnnl_scratchpad_mode_user)
+        .unwrap();
+    attr
+}
+
+/// A single layer GRU over `t` time steps of a batch of `n`, with `c` channels in and
+/// out.
+fn gru(
+    engine: &Arc<Engine>,
+    (t, n, c): (i64, i64, i64),
+    attr: PrimitiveAttributes,
+) -> GruPrimitive {
+    let config = ForwardGruConfig {
+        direction: RnnDirection::LeftToRight,
+        src_layer_desc: new_plain_descriptor(3, vec![t, n, c], DataType::F32),
+        src_iter_desc: None,
+        weights_layer_desc: new_plain_descriptor(5, vec![1, 1, c, G, c], DataType::F32),
+        weights_iter_desc: new_plain_descriptor(5, vec![1, 1, c, G, c], DataType::F32),
+        bias_desc: Some(new_plain_descriptor(4, vec![1, 1, G, c], DataType::F32)),
+        dst_layer_desc: new_plain_descriptor(3, vec![t, n, c], DataType::F32),
+        dst_iter_desc: None,
+        flags: dnnl_rnn_flags_t::dnnl_rnn_flags_undef,
+        attr,
+    };
+
+    Primitive::new::<ForwardGru<_>>(config, engine.clone()).unwrap()
+}
+
+/// Runs `prim` on fixed inputs, passing `scratchpad` if given, and returns the output.
+fn run_gru(
+    prim: &mut GruPrimitive,
+    engine: &Arc<Engine>,
+    stream: &Stream,
+    (t, n, c): (i64, i64, i64),
+    scratchpad: Option<&dyn AnyMemory>,
+) -> Vec<f32> {
+    let new_mem = |dims: Vec<i64>| {
+        let len = dims.iter().product::<i64>() as usize;
+        let data: Vec<f32> = (0..len).map(|i| (i % 7) as f32 * 0.1 - 0.3).collect();
+
+        Memory::new_with_user_buffer(
+            engine.clone(),
+            new_plain_descriptor(dims.len() as i32, dims, DataType::F32),
+            AlignedBuffer::new(&data).unwrap(),
+        )
+        .unwrap()
+    };
+
+    let src_layer_mem = new_mem(vec![t, n, c]);
+    let weights_layer_mem = new_mem(vec![1, 1, c, G, c]);
+    let weights_iter_mem = new_mem(vec![1, 1, c, G, c]);
+    let bias_mem = new_mem(vec![1, 1, G, c]);
+    let dst_layer_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        new_plain_descriptor(3, vec![t, n, c], DataType::F32),
+        AlignedBuffer::<f32>::zeroed((t * n * c) as usize).unwrap(),
+    )
+    .unwrap();
+
+    let mut args = vec![
+        ExecArg {
+            index: DNNL_ARG_SRC_LAYER as i32,
+            mem: &src_layer_mem,
+        },
+        ExecArg {
+            index: DNNL_ARG_WEIGHTS_LAYER as i32,
+            mem: &weights_layer_mem,
+        },
+        ExecArg {
+            index: DNNL_ARG_WEIGHTS_ITER as i32,
+            mem: &weights_iter_mem,
+        },
+        ExecArg {
+            index: DNNL_ARG_BIAS as i32,
+            mem: &bias_mem,
+        },
+        ExecArg {
+            index: DNNL_ARG_DST_LAYER as i32,
+            mem: &dst_layer_mem,
+        },
+    ];
+    if let Some(scratchpad) = scratchpad {
+        args.push(ExecArg::scratchpad(scratchpad));
+    }
+
+    prim.execute(stream, args).unwrap();
+    stream.wait().unwrap();
+
+    dst_layer_mem.to_vec().unwrap()
+}
+
+#[test]
+fn test_shared_user_scratchpad() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. Two GRUs of different sizes that leave their scratchpad to the caller. RNN
+    //    primitives always keep their gates in the scratchpad.
+    let small = (2, 1, 2);
+    let large = (4, 3, 8);
+
+    let mut small_gru = gru(&engine, small, user_scratchpad_attr());
+    let mut large_gru = gru(&engine, large, user_scratchpad_attr());
+
+    let small_scratchpad = small_gru.scratchpad_desc().unwrap().unwrap();
+    let large_scratchpad = large_gru.scratchpad_desc().unwrap().unwrap();
+
+    let small_size = small_scratchpad.get_size();
+    let large_size = large_scratchpad.get_size();
+
+    assert!(small_size > 0);
+    assert!(large_size > 0);
+
+    // ---------------------------------------------------
+    // 2. One scratchpad, as large as the larger of the two.
+    let shared = shared_scratchpad_desc(&[Some(small_scratchpad), Some(large_scratchpad)])
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(shared.get_size(), small_size.max(large_size));
+
+    let scratchpad_mem = Memory::<u8>::new_with_library_buffer(engine.clone(), shared).unwrap();
+
+    // ---------------------------------------------------
+    // 3. Run both, one after the other, on the same scratchpad, and compare with the
+    //    same GRUs managing their own scratchpad.
+    let small_dst = run_gru(
+        &mut small_gru,
+        &engine,
+        &stream,
+        small,
+        Some(&scratchpad_mem),
+    );
+    let large_dst = run_gru(
+        &mut large_gru,
+        &engine,
+        &stream,
+        large,
+        Some(&scratchpad_mem),
+    );
+
+    let mut small_reference = gru(&engine, small, PrimitiveAttributes::new().unwrap());
+    let mut large_reference = gru(&engine, large, PrimitiveAttributes::new().unwrap());
+
+    assert_eq!(
+        small_dst,
+        run_gru(&mut small_reference, &engine, &stream, small, None)
+    );
+    assert_eq!(
+        large_dst,
+        run_gru(&mut large_reference, &engine, &stream, large, None)
+    );
+
+    // The primitives still answer once their descriptors have been handed out.
+    assert_eq!(
+        small_gru
+            .scratchpad_desc()
+            .unwrap()
+            .map(|desc| desc.get_size()),
+        Some(small_size)
+    );
+}
@@ -0,0 +1,130 @@
+use onednnl::{
+    engine::Engine,
+    memory::{
+        buffer::AlignedBuffer,
+        descriptor::{new_plain_descriptor, DataType},
+        Memory,
+    },
+    onednnl_sys::{DNNL_ARG_DIFF_DST, DNNL_ARG_DIFF_SRC, DNNL_ARG_DST, DNNL_ARG_SRC},
+    primitive::{
+        attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor, Backward, ExecArg,
+        Primitive, PropBackwardData, PropForwardTraining,
+    },
+    primitives::shuffle::{
+        BackwardShuffle, BackwardShuffleConfig, ForwardShuffle, ForwardShuffleConfig,
+    },
+    stream::Stream,
+};
+
+#[test]
+fn test_channel_shuffle_forward_backward() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. Six channels of a single pixel, shuffled in groups of two.
+    let md = new_plain_descriptor(4, vec![1, 6, 1, 1], DataType::F32);
+
+    let fwd_config = ForwardShuffleConfig {
+        src_desc: md.clone_desc().unwrap(),
+        dst_desc: md.clone_desc().unwrap(),
+        axis: 1,
+        group_size: 2,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let fwd_pd = PrimitiveDescriptor::<_, PropForwardTraining, _>::new::<ForwardShuffle<_>>(
+        fwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[0.0f32, 1.0, 2.0, 3.0, 4.0, 5.0]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(6).unwrap(),
+    )
+    .unwrap();
+
+    let mut fwd_prim = Primitive::from_descriptor(fwd_pd, engine.clone()).unwrap();
+
+    let fwd_desc = fwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC as i32,
+                    mem: &src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST as i32,
+                    mem: &dst_mem,
+                },
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    stream.wait().unwrap();
+
+    // The first channel of each group, then the second.
+    assert_eq!(
+        dst_mem.to_vec().unwrap(),
+        vec![0.0, 2.0, 4.0, 1.0, 3.0, 5.0]
+    );
+
+    // ---------------------------------------------------
+    // 2. Backward sends each gradient back to the channel it came from.
+    let diff_dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[0.0f32, 1.0, 2.0, 3.0, 4.0, 5.0]).unwrap(),
+    )
+    .unwrap();
+    let diff_src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::<f32>::zeroed(6).unwrap(),
+    )
+    .unwrap();
+
+    let bwd_config = BackwardShuffleConfig {
+        diff_src_desc: md.clone_desc().unwrap(),
+        diff_dst_desc: md,
+        axis: 1,
+        group_size: 2,
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_prim =
+        Primitive::<Backward, PropBackwardData, _>::new::<BackwardShuffle>(bwd_config, engine)
+            .unwrap();
+
+    bwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST as i32,
+                    mem: &diff_dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SRC as i32,
+                    mem: &diff_src_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    assert_eq!(
+        diff_src_mem.to_vec().unwrap(),
+        vec![0.0, 3.0, 1.0, 4.0, 2.0, 5.0]
+    );
+}
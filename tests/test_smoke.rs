@@ -37,6 +37,7 @@ pub fn test_smoke_binary_add() {
         alg_kind: Binary::ADD,
         src0_desc: src0_desc.clone_desc().unwrap(),
         src1_desc: src1_desc.clone_desc().unwrap(),
+        src2_desc: None,
         dst_desc: dst_desc.clone_desc().unwrap(),
         attr: PrimitiveAttributes::new().unwrap(),
     };
@@ -151,7 +152,7 @@ pub fn test_smoke_matmul() {
         .expect("Failed to create weights memory");
 
     // Since we are disabling bias, create a Memory object without a buffer
-    let bias_memory = Memory::new_without_buffer(engine.clone(), zero_bias_desc)
+    let bias_memory = Memory::<f32>::new_without_buffer(engine.clone(), zero_bias_desc)
         .expect("Failed to create bias memory (disabled)");
 
     let dst_memory = Memory::new_with_user_buffer(engine.clone(), dst_desc, output_buffer)
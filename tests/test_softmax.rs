@@ -0,0 +1,191 @@
+mod common;
+
+use {
+    common::assert_close,
+    onednnl::{
+        engine::Engine,
+        memory::{
+            buffer::AlignedBuffer,
+            descriptor::{new_plain_descriptor, DataType},
+            Memory,
+        },
+        onednnl_sys::{DNNL_ARG_DIFF_DST, DNNL_ARG_DIFF_SRC, DNNL_ARG_DST, DNNL_ARG_SRC},
+        primitive::{
+            attributes::PrimitiveAttributes, Backward, ExecArg, Primitive, PropBackwardData,
+            PropForwardInference, PropForwardTraining,
+        },
+        primitives::softmax::{
+            BackwardSoftmax, BackwardSoftmaxConfig, ForwardSoftmax, ForwardSoftmaxConfig, Softmax,
+        },
+        stream::Stream,
+    },
+};
+
+#[test]
+fn test_softmax_forward_backward() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. Two rows normalized over axis 1. exp(ln 3) = 3, so each row
+    //    splits into quarters.
+    let md = new_plain_descriptor(2, vec![2, 2], DataType::F32);
+
+    let ln3 = 3.0f32.ln();
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[0.0f32, ln3, ln3, 0.0]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::zeroed(4).unwrap(),
+    )
+    .unwrap();
+
+    let fwd_config = ForwardSoftmaxConfig {
+        alg_kind: Softmax::ACCURATE,
+        src_desc: md.clone_desc().unwrap(),
+        dst_desc: md.clone_desc().unwrap(),
+        axis: 1,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut fwd_prim = Primitive::<_, PropForwardTraining, _>::new::<ForwardSoftmax<_>>(
+        fwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    let fwd_desc = fwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC as i32,
+                    mem: &src_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST as i32,
+                    mem: &dst_mem,
+                },
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    stream.wait().unwrap();
+
+    assert_close(&dst_mem.to_vec().unwrap(), &[0.25, 0.75, 0.75, 0.25], 1e-6);
+
+    // ---------------------------------------------------
+    // 2. Backward: diff_src = dst * (diff_dst - sum(diff_dst * dst))
+    let diff_dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[1.0f32, 0.0, 1.0, 0.0]).unwrap(),
+    )
+    .unwrap();
+    let diff_src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::zeroed(4).unwrap(),
+    )
+    .unwrap();
+
+    let bwd_config = BackwardSoftmaxConfig {
+        alg_kind: Softmax::ACCURATE,
+        diff_src_desc: md.clone_desc().unwrap(),
+        diff_dst_desc: md.clone_desc().unwrap(),
+        dst_desc: md.clone_desc().unwrap(),
+        axis: 1,
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_prim =
+        Primitive::<Backward, PropBackwardData, _>::new::<BackwardSoftmax>(bwd_config, engine)
+            .unwrap();
+
+    bwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_DST as i32,
+                    mem: &dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST as i32,
+                    mem: &diff_dst_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SRC as i32,
+                    mem: &diff_src_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    assert_close(
+        &diff_src_mem.to_vec().unwrap(),
+        &[0.1875, -0.1875, 0.1875, -0.1875],
+        1e-6,
+    );
+}
+
+#[test]
+fn test_log_softmax_forward() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    let md = new_plain_descriptor(2, vec![1, 2], DataType::F32);
+
+    let src_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::new(&[0.0f32, 3.0f32.ln()]).unwrap(),
+    )
+    .unwrap();
+    let dst_mem = Memory::new_with_user_buffer(
+        engine.clone(),
+        md.clone_desc().unwrap(),
+        AlignedBuffer::zeroed(2).unwrap(),
+    )
+    .unwrap();
+
+    let config = ForwardSoftmaxConfig {
+        alg_kind: Softmax::LOG,
+        src_desc: md.clone_desc().unwrap(),
+        dst_desc: md,
+        axis: 1,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut prim =
+        Primitive::<_, PropForwardInference, _>::new::<ForwardSoftmax<_>>(config, engine).unwrap();
+
+    prim.execute(
+        &stream,
+        vec![
+            ExecArg {
+                index: DNNL_ARG_SRC as i32,
+                mem: &src_mem,
+            },
+            ExecArg {
+                index: DNNL_ARG_DST as i32,
+                mem: &dst_mem,
+            },
+        ],
+    )
+    .unwrap();
+    stream.wait().unwrap();
+
+    assert_close(
+        &dst_mem.to_vec().unwrap(),
+        &[0.25f32.ln(), 0.75f32.ln()],
+        1e-6,
+    );
+}
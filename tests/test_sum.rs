@@ -0,0 +1,106 @@
+use onednnl::{
+    engine::Engine,
+    error::DnnlError,
+    memory::{
+        buffer::AlignedBuffer,
+        descriptor::{new_plain_descriptor, DataType, MemoryDescriptor},
+        Memory,
+    },
+    onednnl_sys::{dnnl_query_t, DNNL_ARG_DST, DNNL_ARG_MULTIPLE_SRC},
+    primitive::{
+        attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor, ExecArg, Forward,
+        Primitive, PropAny,
+    },
+    primitives::sum::{ForwardSum, ForwardSumConfig},
+    stream::Stream,
+};
+
+#[test]
+fn test_sum_with_scales() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    // ---------------------------------------------------
+    // 1. Three [2, 2] sources, letting oneDNN pick the destination.
+    let src_descs: Vec<MemoryDescriptor> = (0..3)
+        .map(|_| new_plain_descriptor(2, vec![2, 2], DataType::F32))
+        .collect();
+    let scales = [1.0, 0.5, -2.0];
+
+    let config = ForwardSumConfig {
+        src_descs: &src_descs,
+        scales: &scales,
+        dst_desc: None,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let pd = PrimitiveDescriptor::<Forward, PropAny, _>::new::<ForwardSum>(config, engine.clone())
+        .unwrap();
+
+    let dst_desc = pd
+        .query_md(dnnl_query_t::dnnl_query_dst_md, 0)
+        .unwrap()
+        .unwrap();
+
+    let src_data: [[f32; 4]; 3] = [
+        [1.0, 2.0, 3.0, 4.0],
+        [2.0, 4.0, 6.0, 8.0],
+        [0.5, 0.0, -0.5, 1.0],
+    ];
+    let src_mems: Vec<Memory<f32>> = src_descs
+        .iter()
+        .zip(&src_data)
+        .map(|(desc, data)| {
+            Memory::new_with_user_buffer(
+                engine.clone(),
+                desc.clone_desc().unwrap(),
+                AlignedBuffer::new(data).unwrap(),
+            )
+            .unwrap()
+        })
+        .collect();
+    let dst_mem = Memory::<f32>::new_with_library_buffer(engine.clone(), dst_desc).unwrap();
+
+    // ---------------------------------------------------
+    // 2. One primitive writes the scaled total.
+    let mut args: Vec<ExecArg<'_>> = src_mems
+        .iter()
+        .enumerate()
+        .map(|(i, mem)| ExecArg {
+            index: DNNL_ARG_MULTIPLE_SRC as i32 + i as i32,
+            mem,
+        })
+        .collect();
+    args.push(ExecArg {
+        index: DNNL_ARG_DST as i32,
+        mem: &dst_mem,
+    });
+
+    let mut prim = Primitive::from_descriptor(pd, engine.clone()).unwrap();
+
+    prim.execute(&stream, args).unwrap();
+    stream.wait().unwrap();
+
+    assert_eq!(dst_mem.to_vec(), Ok(vec![1.0, 4.0, 7.0, 6.0]));
+}
+
+#[test]
+fn test_sum_needs_a_scale_per_source() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+
+    let src_descs = [
+        new_plain_descriptor(1, vec![4], DataType::F32),
+        new_plain_descriptor(1, vec![4], DataType::F32),
+    ];
+
+    let config = ForwardSumConfig {
+        src_descs: &src_descs,
+        scales: &[1.0],
+        dst_desc: Some(new_plain_descriptor(1, vec![4], DataType::F32)),
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let pd = PrimitiveDescriptor::<Forward, PropAny, _>::new::<ForwardSum>(config, engine);
+
+    assert!(matches!(pd, Err(DnnlError::InvalidArguments)));
+}
@@ -0,0 +1,406 @@
+mod common;
+
+use {
+    common::{assert_close, numerical_gradient, to_f32},
+    onednnl::{
+        engine::Engine,
+        memory::{
+            buffer::AlignedBuffer,
+            descriptor::{new_plain_descriptor, DataType, MemoryDescriptor},
+            format_tag::ldgoi,
+            Memory,
+        },
+        onednnl_sys::{
+            dnnl_alg_kind_t, dnnl_rnn_flags_t, DNNL_ARG_BIAS, DNNL_ARG_DIFF_BIAS,
+            DNNL_ARG_DIFF_DST_LAYER, DNNL_ARG_DIFF_SRC_LAYER, DNNL_ARG_DIFF_WEIGHTS_ITER,
+            DNNL_ARG_DIFF_WEIGHTS_LAYER, DNNL_ARG_DST_LAYER, DNNL_ARG_SRC_LAYER,
+            DNNL_ARG_WEIGHTS_ITER, DNNL_ARG_WEIGHTS_LAYER, DNNL_ARG_WORKSPACE,
+        },
+        primitive::{
+            attributes::PrimitiveAttributes, descriptor::PrimitiveDescriptor, Backward, ExecArg,
+            Primitive, PropBackward, PropForwardInference, PropForwardTraining,
+        },
+        primitives::{
+            rnn::RnnDirection,
+            vanilla_rnn::{
+                BackwardVanillaRnn, BackwardVanillaRnnConfig, ForwardVanillaRnn,
+                ForwardVanillaRnnConfig, VanillaRnn,
+            },
+        },
+        stream::Stream,
+    },
+};
+
+// Three time steps of a batch of one through a single left-to-right layer with two
+// channels in and out.
+const T: usize = 3;
+const C: usize = 2;
+
+#[derive(Clone)]
+struct Params {
+    src_layer: Vec<f64>,
+    weights_layer: Vec<f64>,
+    weights_iter: Vec<f64>,
+    bias: Vec<f64>,
+}
+
+impl Params {
+    fn new() -> Self {
+        Params {
+            src_layer: vec![0.5, -0.3, 0.2, 0.8, -0.6, 0.1],
+            weights_layer: vec![0.4, -0.7, 0.3, 0.6],
+            weights_iter: vec![-0.2, 0.5, 0.8, -0.1],
+            bias: vec![0.1, -0.3],
+        }
+    }
+}
+
+fn activate(activation: dnnl_alg_kind_t::Type, alpha: f64, x: f64) -> f64 {
+    match activation {
+        VanillaRnn::RELU if x < 0.0 => alpha * x,
+        VanillaRnn::RELU => x,
+        VanillaRnn::TANH => x.tanh(),
+        VanillaRnn::LOGISTIC => 1.0 / (1.0 + (-x).exp()),
+        _ => unreachable!(),
+    }
+}
+
+/// h = activation(W x + U h_prev + b), with the weights in `ldigo`.
+fn reference_forward(p: &Params, activation: dnnl_alg_kind_t::Type, alpha: f64) -> Vec<f64> {
+    let mut h = vec![0.0; C];
+    let mut dst = Vec::with_capacity(T * C);
+
+    for t in 0..T {
+        let x = &p.src_layer[t * C..][..C];
+        let next: Vec<f64> = (0..C)
+            .map(|o| {
+                let sum: f64 = (0..C)
+                    .map(|i| x[i] * p.weights_layer[i * C + o] + h[i] * p.weights_iter[i * C + o])
+                    .sum();
+
+                activate(activation, alpha, sum + p.bias[o])
+            })
+            .collect();
+
+        dst.extend_from_slice(&next);
+        h = next;
+    }
+
+    dst
+}
+
+struct Descs {
+    src_layer: MemoryDescriptor,
+    weights: MemoryDescriptor,
+    bias: MemoryDescriptor,
+    dst_layer: MemoryDescriptor,
+}
+
+impl Descs {
+    fn new() -> Self {
+        let (t, c) = (T as i64, C as i64);
+
+        Descs {
+            src_layer: new_plain_descriptor(3, vec![t, 1, c], DataType::F32),
+            weights: new_plain_descriptor(5, vec![1, 1, c, 1, c], DataType::F32),
+            bias: new_plain_descriptor(4, vec![1, 1, 1, c], DataType::F32),
+            dst_layer: new_plain_descriptor(3, vec![t, 1, c], DataType::F32),
+        }
+    }
+
+    fn forward_config(
+        &self,
+        activation: dnnl_alg_kind_t::Type,
+        alpha: f32,
+    ) -> ForwardVanillaRnnConfig {
+        ForwardVanillaRnnConfig {
+            activation,
+            direction: RnnDirection::LeftToRight,
+            src_layer_desc: self.src_layer.clone_desc().unwrap(),
+            src_iter_desc: None,
+            weights_layer_desc: self.weights.clone_desc().unwrap(),
+            weights_iter_desc: self.weights.clone_desc().unwrap(),
+            bias_desc: Some(self.bias.clone_desc().unwrap()),
+            dst_layer_desc: self.dst_layer.clone_desc().unwrap(),
+            dst_iter_desc: None,
+            flags: dnnl_rnn_flags_t::dnnl_rnn_flags_undef,
+            alpha,
+            beta: 0.0,
+            attr: PrimitiveAttributes::new().unwrap(),
+        }
+    }
+}
+
+#[test]
+fn test_vanilla_rnn_forward_activations() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    let params = Params::new();
+    let descs = Descs::new();
+
+    let new_mem = |md: &MemoryDescriptor, data: &[f32]| {
+        Memory::new_with_user_buffer(
+            engine.clone(),
+            md.clone_desc().unwrap(),
+            AlignedBuffer::new(data).unwrap(),
+        )
+        .unwrap()
+    };
+
+    let src_layer_mem = new_mem(&descs.src_layer, &to_f32(&params.src_layer));
+    let weights_layer_mem = new_mem(&descs.weights, &to_f32(&params.weights_layer));
+    let weights_iter_mem = new_mem(&descs.weights, &to_f32(&params.weights_iter));
+    let bias_mem = new_mem(&descs.bias, &to_f32(&params.bias));
+
+    // ReLU with a negative slope, so the leaky side is exercised as well.
+    for (activation, alpha) in [
+        (VanillaRnn::RELU, 0.1),
+        (VanillaRnn::TANH, 0.0),
+        (VanillaRnn::LOGISTIC, 0.0),
+    ] {
+        let dst_layer_mem = new_mem(&descs.dst_layer, &[0.0; T * C]);
+
+        let mut prim = Primitive::<_, PropForwardInference, _>::new::<ForwardVanillaRnn<_>>(
+            descs.forward_config(activation, alpha),
+            engine.clone(),
+        )
+        .unwrap();
+
+        prim.execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC_LAYER as i32,
+                    mem: &src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_LAYER as i32,
+                    mem: &weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_ITER as i32,
+                    mem: &weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_BIAS as i32,
+                    mem: &bias_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST_LAYER as i32,
+                    mem: &dst_layer_mem,
+                },
+            ],
+        )
+        .unwrap();
+        stream.wait().unwrap();
+
+        assert_close(
+            &dst_layer_mem.to_vec().unwrap(),
+            &reference_forward(&params, activation, alpha as f64),
+            1e-5,
+        );
+    }
+}
+
+#[test]
+fn test_vanilla_rnn_backward_matches_reference() {
+    let engine = Engine::new(Engine::CPU, 0).unwrap();
+    let stream = Stream::new(engine.clone()).unwrap();
+
+    let params = Params::new();
+    let descs = Descs::new();
+    let diff_dst = [1.0, -0.5, 0.25, 0.75, -1.0, 0.5];
+
+    let new_mem = |md: &MemoryDescriptor, data: &[f32]| {
+        Memory::new_with_user_buffer(
+            engine.clone(),
+            md.clone_desc().unwrap(),
+            AlignedBuffer::new(data).unwrap(),
+        )
+        .unwrap()
+    };
+
+    // ---------------------------------------------------
+    // 1. Forward training, which fills the workspace the backward pass reads.
+    let fwd_pd = PrimitiveDescriptor::<_, PropForwardTraining, _>::new::<ForwardVanillaRnn<_>>(
+        descs.forward_config(VanillaRnn::TANH, 0.0),
+        engine.clone(),
+    )
+    .unwrap();
+
+    let workspace_mem = Memory::<f32>::new_with_library_buffer(
+        engine.clone(),
+        fwd_pd.workspace_desc().unwrap().unwrap(),
+    )
+    .unwrap();
+
+    let src_layer_mem = new_mem(&descs.src_layer, &to_f32(&params.src_layer));
+    let weights_layer_mem = new_mem(&descs.weights, &to_f32(&params.weights_layer));
+    let weights_iter_mem = new_mem(&descs.weights, &to_f32(&params.weights_iter));
+    let bias_mem = new_mem(&descs.bias, &to_f32(&params.bias));
+    let dst_layer_mem = new_mem(&descs.dst_layer, &[0.0; T * C]);
+
+    let mut fwd_prim = Primitive::from_descriptor(fwd_pd, engine.clone()).unwrap();
+
+    let fwd_desc = fwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC_LAYER as i32,
+                    mem: &src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_LAYER as i32,
+                    mem: &weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_ITER as i32,
+                    mem: &weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_BIAS as i32,
+                    mem: &bias_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST_LAYER as i32,
+                    mem: &dst_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WORKSPACE as i32,
+                    mem: &workspace_mem,
+                },
+            ],
+        )
+        .unwrap()
+        .unwrap();
+    stream.wait().unwrap();
+
+    // ---------------------------------------------------
+    // 2. Backward, which reads the weights in ldgoi. With a single gate that is a
+    //    transpose of the input and output channels.
+    let c = C as i64;
+    let weights_ldgoi_md =
+        MemoryDescriptor::new::<5, ldgoi>([1, 1, c, 1, c], DataType::F32).unwrap();
+    let transpose =
+        |w: &[f64]| -> Vec<f32> { (0..C * C).map(|k| w[(k % C) * C + k / C] as f32).collect() };
+
+    let bwd_weights_layer_mem = new_mem(&weights_ldgoi_md, &transpose(&params.weights_layer));
+    let bwd_weights_iter_mem = new_mem(&weights_ldgoi_md, &transpose(&params.weights_iter));
+    let diff_dst_layer_mem = new_mem(&descs.dst_layer, &to_f32(&diff_dst));
+    let diff_src_layer_mem = new_mem(&descs.src_layer, &[0.0; T * C]);
+    let diff_weights_layer_mem = new_mem(&descs.weights, &[0.0; C * C]);
+    let diff_weights_iter_mem = new_mem(&descs.weights, &[0.0; C * C]);
+    let diff_bias_mem = new_mem(&descs.bias, &[0.0; C]);
+
+    let bwd_config = BackwardVanillaRnnConfig {
+        activation: VanillaRnn::TANH,
+        direction: RnnDirection::LeftToRight,
+        src_layer_desc: descs.src_layer.clone_desc().unwrap(),
+        src_iter_desc: None,
+        weights_layer_desc: weights_ldgoi_md.clone_desc().unwrap(),
+        weights_iter_desc: weights_ldgoi_md.clone_desc().unwrap(),
+        bias_desc: Some(descs.bias.clone_desc().unwrap()),
+        dst_layer_desc: descs.dst_layer.clone_desc().unwrap(),
+        dst_iter_desc: None,
+        diff_src_layer_desc: descs.src_layer.clone_desc().unwrap(),
+        diff_src_iter_desc: None,
+        diff_weights_layer_desc: descs.weights.clone_desc().unwrap(),
+        diff_weights_iter_desc: descs.weights.clone_desc().unwrap(),
+        diff_bias_desc: Some(descs.bias.clone_desc().unwrap()),
+        diff_dst_layer_desc: descs.dst_layer.clone_desc().unwrap(),
+        diff_dst_iter_desc: None,
+        flags: dnnl_rnn_flags_t::dnnl_rnn_flags_undef,
+        alpha: 0.0,
+        beta: 0.0,
+        hint_fwd_pd: &fwd_desc,
+        attr: PrimitiveAttributes::new().unwrap(),
+    };
+
+    let mut bwd_prim = Primitive::<Backward, PropBackward, _>::new::<BackwardVanillaRnn<_>>(
+        bwd_config,
+        engine.clone(),
+    )
+    .unwrap();
+
+    bwd_prim
+        .execute(
+            &stream,
+            vec![
+                ExecArg {
+                    index: DNNL_ARG_SRC_LAYER as i32,
+                    mem: &src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_LAYER as i32,
+                    mem: &bwd_weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WEIGHTS_ITER as i32,
+                    mem: &bwd_weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_BIAS as i32,
+                    mem: &bias_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DST_LAYER as i32,
+                    mem: &dst_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_DST_LAYER as i32,
+                    mem: &diff_dst_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_WORKSPACE as i32,
+                    mem: &workspace_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_SRC_LAYER as i32,
+                    mem: &diff_src_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_WEIGHTS_LAYER as i32,
+                    mem: &diff_weights_layer_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_WEIGHTS_ITER as i32,
+                    mem: &diff_weights_iter_mem,
+                },
+                ExecArg {
+                    index: DNNL_ARG_DIFF_BIAS as i32,
+                    mem: &diff_bias_mem,
+                },
+            ],
+        )
+        .unwrap();
+    stream.wait().unwrap();
+
+    // ---------------------------------------------------
+    // 3. Compare with central differences of sum(dst_layer * diff_dst).
+    let forward: fn(&Params) -> Vec<f64> = |p| reference_forward(p, VanillaRnn::TANH, 0.0);
+
+    let tolerance = 1e-4;
+
+    assert_close(
+        &diff_src_layer_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.src_layer, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_weights_layer_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.weights_layer, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_weights_iter_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.weights_iter, forward, &diff_dst),
+        tolerance,
+    );
+    assert_close(
+        &diff_bias_mem.to_vec().unwrap(),
+        &numerical_gradient(&params, |p| &mut p.bias, forward, &diff_dst),
+        tolerance,
+    );
+}